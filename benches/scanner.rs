@@ -0,0 +1,87 @@
+//!Scanner throughput benchmark
+//!
+//!Scans synthetic documents of doubling size into owned and borrowed tokens, and into borrowed
+//!tokens with a structural index built first, and prints the time taken and throughput for each.
+//!The tokens are counted rather than collected, so the numbers are not dominated by growing a
+//!`Vec` of them. With a linear scanner the MB/s columns stay roughly flat as the input grows.
+//!
+//!```text
+//!cargo bench --bench scanner
+//!```
+
+use std::time::{Duration, Instant};
+
+use jtool::scanner::{scanner::Scanner, scanner_config::ScannerConfig};
+
+const RECORD: &str = r#"{"id": 9007199254740993, "name": "café \"bar\"", "tags": ["a", "b"], "price": -12.5, "active": true, "parent": null},
+"#;
+
+const SIZES: [usize; 5] = [1, 2, 4, 8, 16];
+const RUNS: u32 = 5;
+
+fn document(megabytes: usize) -> String {
+    let target = megabytes * 1024 * 1024;
+    let mut document = String::with_capacity(target + RECORD.len() + 2);
+
+    document.push('[');
+    while document.len() < target {
+        document.push_str(RECORD);
+    }
+    document.push_str("null]");
+
+    document
+}
+
+fn time(scan: impl Fn() -> usize) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let count = scan();
+            let elapsed = start.elapsed();
+
+            assert!(count > 0);
+
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+fn count(scanner: Scanner) -> usize {
+    scanner.map(Result::unwrap).count()
+}
+
+fn count_borrowed(mut scanner: Scanner) -> usize {
+    std::iter::from_fn(|| scanner.next_borrowed())
+        .map(Result::unwrap)
+        .count()
+}
+
+fn throughput(source: &str, elapsed: Duration) -> f64 {
+    source.len() as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64()
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>12} {:>10} {:>12} {:>10} {:>12} {:>10}",
+        "size", "owned", "MB/s", "borrowed", "MB/s", "indexed", "MB/s"
+    );
+
+    for megabytes in SIZES {
+        let source = document(megabytes);
+        let owned = time(|| count(Scanner::new(&source)));
+        let borrowed = time(|| count_borrowed(Scanner::new(&source)));
+        let indexed = time(|| count_borrowed(Scanner::indexed(&source, ScannerConfig::default())));
+
+        println!(
+            "{:>6}MB {:>10.2}ms {:>10.1} {:>10.2}ms {:>10.1} {:>10.2}ms {:>10.1}",
+            megabytes,
+            owned.as_secs_f64() * 1000.0,
+            throughput(&source, owned),
+            borrowed.as_secs_f64() * 1000.0,
+            throughput(&source, borrowed),
+            indexed.as_secs_f64() * 1000.0,
+            throughput(&source, indexed),
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt::Write;
+
+use crate::{
+    parser::{parser_error::ParserError, value::write_escaped},
+    scanner::scanner_error::ScannerError,
+    token::token_position::TokenPosition,
+};
+
+use super::{severity::Severity, suggestion::Suggestion};
+
+///A message about a span of the source, ready to be rendered against it
+///
+///# Examples
+///
+///```
+///# use jtool::{diagnostic::diagnostic::Diagnostic, scanner::scanner::Scanner};
+///let error = Scanner::new("[01]").scan().unwrap_err();
+///let diagnostic = Diagnostic::from(&error);
+///
+///assert_eq!("Leading zero in number [01]", diagnostic.message);
+///assert_eq!(Some("starts with a zero".to_string()), diagnostic.label);
+///assert_eq!(Some("J0010"), diagnostic.code);
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    ///Stable code of the error e.g. `J0003`, see [`ScannerError::code`]
+    pub code: Option<&'static str>,
+    pub severity: Severity,
+    pub message: String,
+    pub position: TokenPosition,
+    ///Shown next to the underline
+    pub label: Option<String>,
+    ///Shown after the snippet, one note per line
+    pub help: Vec<String>,
+    ///Fix that can be applied to the source to resolve the diagnostic
+    pub suggestion: Option<Suggestion>,
+    ///Other places in the source related to the diagnostic, each shown with its own snippet
+    pub notes: Vec<Note>,
+}
+
+///A message about another span of the source, e.g. where a duplicate key was first defined
+#[derive(Debug, PartialEq, Clone)]
+pub struct Note {
+    pub message: String,
+    pub position: TokenPosition,
+}
+
+impl Note {
+    pub fn new(message: impl Into<String>, position: TokenPosition) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>, position: TokenPosition) -> Self {
+        Self {
+            code: None,
+            severity: Severity::Error,
+            message: message.into(),
+            position,
+            label: None,
+            help: vec![],
+            suggestion: None,
+            notes: vec![],
+        }
+    }
+
+    ///Serialise as a single line JSON record for tooling
+    ///
+    ///Every field is always present, with `null` for a missing code, label or suggestion:
+    ///
+    ///```text
+    ///{"code":"J0003","severity":"error","message":"Unterminated string","position":{"line_start":1,...},"label":"missing a closing quote","help":[...],"suggestion":null,"notes":[]}
+    ///```
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"code\":");
+        match self.code {
+            Some(code) => write_json_string(&mut json, code),
+            None => json.push_str("null"),
+        }
+
+        json.push_str(",\"severity\":");
+        write_json_string(&mut json, self.severity.as_str());
+        json.push_str(",\"message\":");
+        write_json_string(&mut json, &self.message);
+
+        json.push_str(",\"position\":");
+        write_json_position(&mut json, &self.position);
+
+        json.push_str(",\"label\":");
+        match &self.label {
+            Some(label) => write_json_string(&mut json, label),
+            None => json.push_str("null"),
+        }
+
+        json.push_str(",\"help\":[");
+        for (index, help) in self.help.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            write_json_string(&mut json, help);
+        }
+        json.push(']');
+
+        json.push_str(",\"suggestion\":");
+        match &self.suggestion {
+            Some(suggestion) => {
+                json.push_str("{\"replacement\":");
+                write_json_string(&mut json, &suggestion.replacement);
+                json.push_str(",\"applicability\":");
+                write_json_string(&mut json, suggestion.applicability.as_str());
+                json.push_str(",\"position\":");
+                write_json_position(&mut json, &suggestion.position);
+                json.push('}');
+            }
+            None => json.push_str("null"),
+        }
+
+        json.push_str(",\"notes\":[");
+        for (index, note) in self.notes.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"message\":");
+            write_json_string(&mut json, &note.message);
+            json.push_str(",\"position\":");
+            write_json_position(&mut json, &note.position);
+            json.push('}');
+        }
+        json.push_str("]}");
+
+        json
+    }
+}
+
+impl From<&ScannerError> for Diagnostic {
+    fn from(error: &ScannerError) -> Self {
+        Self {
+            code: Some(error.code()),
+            label: error.label(),
+            help: error.help(),
+            suggestion: error.suggestion().cloned(),
+            ..Self::new(error.message(), *error.position())
+        }
+    }
+}
+
+impl From<&ParserError> for Diagnostic {
+    fn from(error: &ParserError) -> Self {
+        Self {
+            code: Some(error.code()),
+            label: error.label(),
+            help: error.help(),
+            suggestion: error.suggestion().cloned(),
+            notes: error.notes(),
+            ..Self::new(error.message(), *error.position())
+        }
+    }
+}
+
+///Append `position` as a JSON object with a field per coordinate
+fn write_json_position(json: &mut String, position: &TokenPosition) {
+    let TokenPosition {
+        line_start,
+        line_end,
+        column_start,
+        column_end,
+        offset_start,
+        offset_end,
+    } = position;
+
+    let _ = write!(
+        json,
+        "{{\"line_start\":{},\"line_end\":{},\"column_start\":{},\"column_end\":{},\"offset_start\":{},\"offset_end\":{}}}",
+        line_start, line_end, column_start, column_end, offset_start, offset_end
+    );
+}
+
+///Append `text` as a quoted JSON string
+fn write_json_string(json: &mut String, text: &str) {
+    let _ = write_escaped(json, text);
+}
+
+#[cfg(test)]
+mod diagnostic_tests {
+    use crate::{
+        diagnostic::severity::Severity,
+        scanner::{scanner::Scanner, scanner_error::ScannerError},
+        token::token_position::TokenPosition,
+    };
+
+    use super::Diagnostic;
+
+    #[test]
+    fn diagnostic_from_scanner_error() {
+        let error = ScannerError::ControlCharacter(TokenPosition::new(1, 1, 3, 4, 2, 3), '\t');
+
+        assert_eq!(
+            Diagnostic {
+                code: Some("J0004"),
+                severity: Severity::Error,
+                message: "Unescaped control character [U+0009] in string".to_string(),
+                position: TokenPosition::new(1, 1, 3, 4, 2, 3),
+                label: Some("must be escaped".to_string()),
+                help: vec!["write it as `\\t`".to_string()],
+                suggestion: None,
+                notes: vec![],
+            },
+            Diagnostic::from(&error)
+        );
+
+        let error = ScannerError::Io(TokenPosition::new(1, 1, 1, 2, 0, 1), "gone".to_string());
+        let diagnostic = Diagnostic::from(&error);
+
+        assert_eq!(None, diagnostic.label);
+        assert!(diagnostic.help.is_empty());
+    }
+
+    #[test]
+    fn diagnostic_to_json() {
+        let error = ScannerError::UnterminatedString(TokenPosition::new(2, 2, 3, 6, 7, 10));
+
+        assert_eq!(
+            r#"{"code":"J0003","severity":"error","message":"Unterminated string","position":{"line_start":2,"line_end":2,"column_start":3,"column_end":6,"offset_start":7,"offset_end":10},"label":"missing a closing quote","help":["add a `\"` where the string should end"],"suggestion":null,"notes":[]}"#,
+            Diagnostic::from(&error).to_json()
+        );
+
+        let mut diagnostic =
+            Diagnostic::new("tab\there \u{1}", TokenPosition::new(1, 1, 1, 1, 0, 0));
+        diagnostic.severity = Severity::Warning;
+
+        assert_eq!(
+            r#"{"code":null,"severity":"warning","message":"tab\there \u0001","position":{"line_start":1,"line_end":1,"column_start":1,"column_end":1,"offset_start":0,"offset_end":0},"label":null,"help":[],"suggestion":null,"notes":[]}"#,
+            diagnostic.to_json()
+        );
+    }
+
+    #[test]
+    fn suggestion_to_json() {
+        let error = Scanner::new("[Null]").scan().unwrap_err();
+
+        assert!(Diagnostic::from(&error).to_json().ends_with(
+            r#""help":["did you mean `null`?"],"suggestion":{"replacement":"null","applicability":"machine-applicable","position":{"line_start":1,"line_end":1,"column_start":2,"column_end":6,"offset_start":1,"offset_end":5}},"notes":[]}"#
+        ));
+    }
+}
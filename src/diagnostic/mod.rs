@@ -0,0 +1,5 @@
+#[allow(clippy::module_inception)]
+pub mod diagnostic;
+pub mod renderer;
+pub mod severity;
+pub mod suggestion;
@@ -0,0 +1,444 @@
+use crate::{
+    source_map::{
+        column_unit::{ColumnCounter, ColumnUnit},
+        source_map::SourceMap,
+    },
+    token::token_position::TokenPosition,
+};
+
+use super::{diagnostic::Diagnostic, severity::Severity};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+const ELLIPSIS: &str = "...";
+
+///Spans covering more lines than this only show their first and last lines
+const MAX_SPAN_LINES: usize = 4;
+
+///Render diagnostics against the source they were found in, in the style of rustc
+///
+///```text
+///error[J0003]: Unterminated string
+/// --> data.json:1:5
+///  |
+///1 | [1, "abc
+///  |     ^^^^ missing a closing quote
+///  |
+///  = help: add a `"` where the string should end
+///```
+///
+///Columns are measured in terminal cells with tabs expanded so that the underline lines up with
+///the text above it. Lines wider than `max_width` are trimmed to a window around the span.
+///
+///# Examples
+///
+///```
+///# use jtool::{diagnostic::{diagnostic::Diagnostic, renderer::Renderer}, scanner::scanner::Scanner};
+///let source = "[1, tru]";
+///let error = Scanner::new(source).scan().unwrap_err();
+///
+///let rendered = Renderer::new(source).render(&Diagnostic::from(&error));
+///
+///assert!(rendered.contains("1 | [1, tru]\n  |     ^^^ not a JSON value"));
+///```
+#[derive(Debug)]
+pub struct Renderer<'a> {
+    source_map: SourceMap<'a>,
+    ///Name of the source shown after the arrow, e.g. a file path
+    pub origin: Option<String>,
+    ///Colour the output with ANSI escape codes
+    pub color: bool,
+    ///Width in cells a source line is trimmed to, not counting the gutter
+    pub max_width: usize,
+    pub tab_size: usize,
+}
+
+///A line of source laid out in terminal cells
+struct Layout {
+    text: String,
+    caret_start: usize,
+    caret_end: usize,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source_map: SourceMap::new(source),
+            origin: None,
+            color: false,
+            max_width: 100,
+            tab_size: 4,
+        }
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let accent = match diagnostic.severity {
+            Severity::Warning => YELLOW,
+            Severity::Error => RED,
+        };
+        let title = match diagnostic.code {
+            Some(code) => format!("{}[{}]", diagnostic.severity, code),
+            None => diagnostic.severity.to_string(),
+        };
+
+        let mut lines = vec![format!(
+            "{}: {}",
+            self.paint(accent, &title),
+            self.paint(BOLD, &diagnostic.message)
+        )];
+
+        let pad = self.snippet(
+            &mut lines,
+            diagnostic.position,
+            diagnostic.label.as_deref(),
+            accent,
+            "^",
+        );
+
+        match &pad {
+            Some(pad) => {
+                if !diagnostic.help.is_empty() {
+                    lines.push(format!("{} {}", pad, self.paint(BLUE, "|")));
+                }
+
+                for help in &diagnostic.help {
+                    lines.push(format!(
+                        "{} {} {}",
+                        pad,
+                        self.paint(BLUE, "="),
+                        format_args!("{}: {}", self.paint(BOLD, "help"), help)
+                    ));
+                }
+            }
+            None => lines.extend(diagnostic.help.iter().map(|help| format!("help: {}", help))),
+        }
+
+        for note in &diagnostic.notes {
+            lines.push(format!("{}: {}", self.paint(BOLD, "note"), note.message));
+            self.snippet(&mut lines, note.position, None, BLUE, "-");
+        }
+
+        lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    ///Append the source lines under `position` to `lines`, underlined with `marker`
+    ///
+    ///Returns the padding of the gutter, or `None` when the position is not in the source and
+    ///nothing was rendered.
+    fn snippet(
+        &self,
+        lines: &mut Vec<String>,
+        position: TokenPosition,
+        label: Option<&str>,
+        accent: &str,
+        marker: &str,
+    ) -> Option<String> {
+        let start = self
+            .source_map
+            .location(position.offset_start)
+            .filter(|_| position.offset_start <= position.offset_end)
+            .filter(|_| position.offset_end <= self.source_map.source().len())?;
+
+        let mut line_end = start.line;
+        while let Some(next) = self.source_map.line_start(line_end + 1) {
+            if next >= position.offset_end {
+                break;
+            }
+
+            line_end += 1;
+        }
+
+        let shown: Vec<Option<usize>> = if line_end - start.line < MAX_SPAN_LINES {
+            (start.line..=line_end).map(Some).collect()
+        } else {
+            vec![Some(start.line), Some(start.line + 1), None, Some(line_end)]
+        };
+
+        let gutter = line_end.to_string().len();
+        let pad = " ".repeat(gutter);
+        let bar = self.paint(BLUE, "|");
+
+        let location = match &self.origin {
+            Some(origin) => format!("{}:{}:{}", origin, start.line, start.column),
+            None => format!("{}:{}", start.line, start.column),
+        };
+
+        lines.push(format!("{}{} {}", pad, self.paint(BLUE, "-->"), location));
+        lines.push(format!("{} {}", pad, bar));
+
+        for line in &shown {
+            let Some(line) = *line else {
+                lines.push(self.paint(BLUE, ELLIPSIS));
+                continue;
+            };
+
+            let layout = self.layout(line, position.offset_start, position.offset_end);
+            let number = self.paint(BLUE, &format!("{:>width$}", line, width = gutter));
+
+            lines.push(format!("{} {} {}", number, bar, layout.text));
+
+            let mut underline = format!(
+                "{}{}",
+                " ".repeat(layout.caret_start),
+                self.paint(
+                    accent,
+                    &marker.repeat(layout.caret_end.saturating_sub(layout.caret_start).max(1))
+                )
+            );
+
+            if let (true, Some(label)) = (line == line_end, label) {
+                underline = format!("{} {}", underline, self.paint(accent, label));
+            }
+
+            lines.push(format!("{} {} {}", pad, bar, underline));
+        }
+
+        Some(pad)
+    }
+
+    ///Lay out `line` with the part of `offset_start..offset_end` on it underlined
+    ///
+    ///The line is walked twice, once to measure it and once to copy out the visible part, so a
+    ///very long line costs no more memory than what is shown of it.
+    fn layout(&self, line: usize, offset_start: usize, offset_end: usize) -> Layout {
+        let line_start = self.source_map.line_start(line).unwrap_or_default();
+        let text = self.source_map.line(line).unwrap_or_default();
+
+        let span_start = offset_start.saturating_sub(line_start).min(text.len());
+        let span_end = offset_end
+            .saturating_sub(line_start)
+            .clamp(span_start, text.len());
+
+        let (mut caret_start, mut caret_end) = (None, None);
+        let mut width = 0;
+
+        for (index, start, end, _) in self.cells(line_start, text) {
+            if caret_start.is_none() && index >= span_start {
+                caret_start = Some(start);
+            }
+            if caret_end.is_none() && index >= span_end {
+                caret_end = Some(start);
+            }
+
+            width = end;
+        }
+
+        let caret_start = caret_start.unwrap_or(width);
+        let caret_end = caret_end.unwrap_or(width);
+
+        let (window_start, window_end) = if width <= self.max_width {
+            (0, width)
+        } else {
+            let window = self.max_width.saturating_sub(2 * ELLIPSIS.len()).max(1);
+            let context = (window - (caret_end - caret_start).min(window)) / 2;
+            let window_end = (caret_start.saturating_sub(context) + window).min(width);
+
+            (window_end.saturating_sub(window), window_end)
+        };
+
+        let prefix = if window_start > 0 { ELLIPSIS } else { "" };
+        let suffix = if window_end < width { ELLIPSIS } else { "" };
+
+        let mut visible = prefix.to_string();
+
+        for (_, start, end, char) in self.cells(line_start, text) {
+            if start < window_start || end > window_end {
+                continue;
+            }
+
+            match char {
+                '\t' => visible.extend(std::iter::repeat_n(' ', end - start)),
+                _ if char.is_control() => visible.push('\u{fffd}'),
+                _ => visible.push(char),
+            }
+        }
+
+        visible.push_str(suffix);
+
+        let shift =
+            |cell: usize| cell.clamp(window_start, window_end) - window_start + prefix.len();
+
+        Layout {
+            text: visible,
+            caret_start: shift(caret_start),
+            caret_end: shift(caret_end),
+        }
+    }
+
+    ///The characters of the line `text` starting at `line_start`, each with its byte index and
+    ///the cells it covers, with tabs expanded and a leading byte order mark skipped
+    fn cells<'t>(
+        &self,
+        line_start: usize,
+        text: &'t str,
+    ) -> impl Iterator<Item = (usize, usize, usize, char)> + 't {
+        let unit = ColumnUnit::Display {
+            tab_size: self.tab_size,
+        };
+        let mut counter = ColumnCounter::new();
+        let mut column = 1;
+
+        text.char_indices()
+            .filter(move |(index, char)| line_start + index != 0 || *char != '\u{feff}')
+            .map(move |(index, char)| {
+                let next = if char.is_control() && char != '\t' {
+                    counter.reset();
+                    column + 1
+                } else {
+                    counter.advance(unit, column, char)
+                };
+
+                let cell = (index, column - 1, next - 1, char);
+                column = next;
+                cell
+            })
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod renderer_tests {
+    use crate::{
+        diagnostic::diagnostic::Diagnostic,
+        parser::parser::Parser,
+        scanner::{scanner::Scanner, scanner_config::ScannerConfig},
+        token::token_position::TokenPosition,
+    };
+
+    use super::Renderer;
+
+    fn render(source: &str) -> String {
+        let error = Scanner::new(source).scan().unwrap_err();
+
+        Renderer::new(source).render(&Diagnostic::from(&error))
+    }
+
+    #[test]
+    fn render_single_line() {
+        assert_eq!(
+            "error[J0003]: Unterminated string\n --> 1:5\n  |\n1 | [1, \"abc\n  |     ^^^^ missing a closing quote\n  |\n  = help: add a `\"` where the string should end",
+            render("[1, \"abc")
+        );
+    }
+
+    #[test]
+    fn render_with_origin_and_gutter() {
+        let source = "[\n\n\n\n\n\n\n\n\n 01]";
+        let error = Scanner::new(source).scan().unwrap_err();
+        let mut renderer = Renderer::new(source);
+        renderer.origin = Some("data.json".to_string());
+
+        assert_eq!(
+            "error[J0010]: Leading zero in number [01]\n  --> data.json:10:2\n   |\n10 |  01]\n   |  ^^ starts with a zero\n   |\n   = help: remove the leading zeros",
+            renderer.render(&Diagnostic::from(&error))
+        );
+    }
+
+    #[test]
+    fn render_tabs_and_wide_characters() {
+        let rendered = render("{\"中\":\t+1}");
+
+        assert!(rendered.contains("1 | {\"中\":  +1}\n  |         ^^ starts with `+`"));
+    }
+
+    #[test]
+    fn render_zero_width_span() {
+        let config = ScannerConfig {
+            require_value: true,
+            ..Default::default()
+        };
+        let source = "  ";
+        let error = Scanner::with_config(source, config).scan().unwrap_err();
+
+        assert!(Renderer::new(source)
+            .render(&Diagnostic::from(&error))
+            .contains("1 |\n  |   ^ expected a value"));
+    }
+
+    #[test]
+    fn render_multi_line_span() {
+        let mut diagnostic = Diagnostic::new("Spans lines", TokenPosition::new(1, 3, 2, 1, 1, 7));
+        diagnostic.label = Some("here".to_string());
+
+        assert_eq!(
+            "error: Spans lines\n --> 1:2\n  |\n1 | [1,\n  |  ^^\n2 | 2,\n  | ^^ here",
+            Renderer::new("[1,\n2,\n3]").render(&diagnostic)
+        );
+
+        let source = "[\n1,\n2,\n3,\n4,\n5]";
+        let diagnostic = Diagnostic::new("Long span", TokenPosition::new(1, 6, 1, 3, 0, 16));
+        let rendered = Renderer::new(source).render(&diagnostic);
+
+        assert!(rendered.contains("2 | 1,\n  | ^^\n...\n6 | 5]\n  | ^^"));
+        assert!(!rendered.contains("3 |"));
+    }
+
+    #[test]
+    fn render_trims_long_lines() {
+        let source = format!("[{}tru{}]", "1, ".repeat(40), ", 2".repeat(40));
+        let error = Scanner::new(&source).scan().unwrap_err();
+        let mut renderer = Renderer::new(&source);
+        renderer.max_width = 26;
+
+        assert_eq!(
+            "error[J0002]: Unknown literal [tru]\n --> 1:122\n  |\n1 | ..., 1, 1, tru, 2, 2, 2...\n  |            ^^^ not a JSON value\n  |\n  = help: did you mean `true`?",
+            renderer.render(&Diagnostic::from(&error))
+        );
+    }
+
+    #[test]
+    fn render_colors() {
+        let source = "@";
+        let error = Scanner::new(source).scan().unwrap_err();
+        let mut renderer = Renderer::new(source);
+        renderer.color = true;
+
+        let rendered = renderer.render(&Diagnostic::from(&error));
+
+        assert!(rendered
+            .starts_with("\x1b[1;31merror[J0001]\x1b[0m: \x1b[1mUnknown character [@]\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;31m^\x1b[0m \x1b[1;31mnot valid here\x1b[0m"));
+    }
+
+    #[test]
+    fn render_notes() {
+        let source = "{\n  \"a\": 1,\n  \"a\": 2\n}";
+        let error = Parser::new(source).parse().unwrap_err();
+
+        assert_eq!(
+            "error[J0104]: Duplicate key [a]\n --> 3:3\n  |\n3 |   \"a\": 2\n  |   ^^^ used again here\n  |\n  = help: remove or rename one of the members\nnote: first used here\n --> 2:3\n  |\n2 |   \"a\": 1,\n  |   ---",
+            Renderer::new(source).render(&Diagnostic::from(&error))
+        );
+    }
+
+    #[test]
+    fn render_invalid_position() {
+        let mut diagnostic = Diagnostic::new("Lost", TokenPosition::new(1, 1, 9, 10, 8, 9));
+        diagnostic.help = vec!["look elsewhere".to_string()];
+
+        assert_eq!(
+            "error: Lost\nhelp: look elsewhere",
+            Renderer::new("[]").render(&diagnostic)
+        );
+    }
+
+    #[test]
+    fn render_skips_byte_order_mark() {
+        assert!(render("\u{feff}[01]").contains("1 | [01]\n  |  ^^ starts with a zero"));
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt::Display;
+
+///How serious a diagnostic is
+///
+///Errors stop a document from being read while warnings flag input that was accepted but is
+///probably a mistake.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum Severity {
+    Warning,
+    #[default]
+    Error,
+}
+
+impl Severity {
+    ///Lowercase name used in rendered and JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod severity_tests {
+    use super::Severity;
+
+    #[test]
+    fn severity_order_and_names() {
+        assert!(Severity::Error > Severity::Warning);
+        assert_eq!(Severity::Error, Severity::default());
+        assert_eq!("warning", Severity::Warning.to_string());
+    }
+}
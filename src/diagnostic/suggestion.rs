@@ -0,0 +1,141 @@
+use crate::token::token_position::TokenPosition;
+
+///How safe it is to apply a suggestion without a human looking at it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Applicability {
+    ///The replacement is almost certainly what was meant and can be applied automatically
+    MachineApplicable,
+    ///The replacement is a guess that should be confirmed first
+    MaybeIncorrect,
+}
+
+impl Applicability {
+    ///Kebab-case name used in JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MachineApplicable => "machine-applicable",
+            Self::MaybeIncorrect => "maybe-incorrect",
+        }
+    }
+}
+
+///A proposed fix that replaces the text under `position` with `replacement`
+///
+///# Examples
+///
+///```
+///# use jtool::{diagnostic::suggestion::Applicability, scanner::scanner::Scanner};
+///let source = "[tru]";
+///let error = Scanner::new(source).scan().unwrap_err();
+///let suggestion = error.suggestion().unwrap();
+///
+///assert_eq!("true", suggestion.replacement);
+///assert_eq!(Applicability::MachineApplicable, suggestion.applicability);
+///assert_eq!(Some("[true]".to_string()), suggestion.apply(source));
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Suggestion {
+    pub position: TokenPosition,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn new(
+        position: TokenPosition,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            position,
+            replacement: replacement.into(),
+            applicability,
+        }
+    }
+
+    ///Rewrite `source` with the suggestion applied, failing if the position is not in the source
+    pub fn apply(&self, source: &str) -> Option<String> {
+        let before = source.get(..self.position.offset_start)?;
+        let after = source.get(self.position.offset_end..)?;
+
+        Some(format!("{}{}{}", before, self.replacement, after))
+    }
+}
+
+///Number of single character insertions, deletions, substitutions and swaps of adjacent
+///characters needed to turn `a` into `b`, or `None` if it is more than `max`
+///
+///This is the optimal string alignment distance, so `flase` is one edit away from `false`. Words
+///whose lengths differ by more than `max` are rejected before any work is done, and only the last
+///three rows of the distance matrix are kept.
+pub fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    if a.chars().count().abs_diff(b.chars().count()) > max {
+        return None;
+    }
+
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        row[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            let mut distance = (previous[j] + 1)
+                .min(row[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(before[j - 2] + 1);
+            }
+
+            row[j] = distance;
+        }
+
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut row);
+    }
+
+    Some(previous[b.len()]).filter(|distance| *distance <= max)
+}
+
+#[cfg(test)]
+mod suggestion_tests {
+    use crate::token::token_position::TokenPosition;
+
+    use super::{edit_distance, Applicability, Suggestion};
+
+    #[test]
+    fn distances() {
+        assert_eq!(Some(0), edit_distance("null", "null", 2));
+        assert_eq!(Some(1), edit_distance("tru", "true", 2));
+        assert_eq!(Some(1), edit_distance("flase", "false", 2));
+        assert_eq!(Some(1), edit_distance("nulll", "null", 2));
+        assert_eq!(Some(2), edit_distance("nil", "null", 2));
+        assert_eq!(Some(2), edit_distance("abcd", "badc", 2));
+        assert_eq!(Some(4), edit_distance("", "true", 4));
+        assert_eq!(None, edit_distance("", "true", 3));
+        assert_eq!(None, edit_distance("fasle", "true", 2));
+        assert_eq!(None, edit_distance(&"a".repeat(1 << 20), "null", 2));
+    }
+
+    #[test]
+    fn apply() {
+        let suggestion = Suggestion::new(
+            TokenPosition::new(1, 1, 6, 10, 5, 9),
+            "null",
+            Applicability::MaybeIncorrect,
+        );
+
+        assert_eq!(
+            Some("{\"a\":null}".to_string()),
+            suggestion.apply("{\"a\":None}")
+        );
+        assert_eq!(None, suggestion.apply("{}"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod diagnostic;
+pub mod parser;
+pub mod scanner;
+pub mod source_map;
+pub mod token;
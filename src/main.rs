@@ -1,6 +1,67 @@
-mod scanner;
-mod token;
+use std::{
+    env, fs,
+    fs::File,
+    io::{self, IsTerminal},
+    process,
+};
+
+use jtool::{
+    diagnostic::{diagnostic::Diagnostic, renderer::Renderer},
+    scanner::{encoding::decode, scanner_error::ScannerError, stream_scanner::StreamScanner},
+    token::token::Token,
+};
 
 fn main() {
-    println!("Hello, world!");
+    let mut json = false;
+    let mut path = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ => path = Some(arg),
+        }
+    }
+
+    let result = match &path {
+        Some(path) => {
+            let file = File::open(path).unwrap_or_else(|error| {
+                eprintln!("{}: {}", path, error);
+                process::exit(2);
+            });
+
+            print_tokens(StreamScanner::new(file))
+        }
+        None => print_tokens(StreamScanner::new(io::stdin().lock())),
+    };
+
+    if let Err(error) = result {
+        if json {
+            eprintln!("{}", Diagnostic::from(&error).to_json());
+            process::exit(1);
+        }
+
+        match path.and_then(|path| Some((fs::read(&path).ok()?, path))) {
+            Some((bytes, path)) => {
+                let source = decode(&bytes).unwrap_or_else(|_| String::from_utf8_lossy(&bytes));
+                let mut renderer = Renderer::new(&source);
+                renderer.origin = Some(path);
+                renderer.color = io::stderr().is_terminal();
+
+                eprintln!("{}", renderer.render(&Diagnostic::from(&error)));
+            }
+            None => eprintln!("{}", error),
+        }
+
+        process::exit(1);
+    }
+}
+
+fn print_tokens(
+    tokens: impl Iterator<Item = Result<Token, ScannerError>>,
+) -> Result<(), ScannerError> {
+    for token in tokens {
+        println!("{:?}", token?);
+    }
+
+    Ok(())
 }
@@ -0,0 +1,29 @@
+///What a [`Parser`](super::parser::Parser) does with an object that has the same key twice
+///
+///RFC 8259 leaves the meaning of duplicate keys to the implementation, and consumers that disagree
+///on which member counts can be played against each other, so they are rejected by default.
+///
+///# Examples
+///
+///```
+///# use jtool::parser::{duplicate_keys::DuplicateKeys, parser::Parser};
+///let mut parser = Parser::new(r#"{"role": "user", "role": "admin"}"#);
+///parser.duplicate_keys = DuplicateKeys::FirstWins;
+///
+///let value = parser.parse().unwrap();
+///
+///assert_eq!(Some("user"), value.get("role").and_then(|role| role.as_str()));
+///```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DuplicateKeys {
+    ///Fail with [`ParserError::DuplicateKey`](super::parser_error::ParserError::DuplicateKey)
+    #[default]
+    Error,
+    ///Keep the last member like [`DuplicateKeys::LastWins`], recording a warning in
+    ///[`Parser::warnings`](super::parser::Parser::warnings)
+    Warn,
+    ///Keep the first member and ignore the rest
+    FirstWins,
+    ///Keep the value of the last member, in the place of the first as JavaScript does
+    LastWins,
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use super::value::Value;
+
+///The members of a JSON object, kept in the order they were inserted
+///
+///Members live in a `Vec` so iteration follows the source, with an index from key to position
+///alongside for constant time lookup. Nothing reorders the members except [`Map::sort_keys`].
+///
+///Two maps are equal when they have the same members, whatever their order.
+///
+///# Examples
+///
+///```
+///# use jtool::parser::{map::Map, value::Value};
+///let mut map = Map::new();
+///map.insert("zebra".to_string(), Value::Null);
+///map.insert("apple".to_string(), Value::Bool(true));
+///
+///assert_eq!(vec!["zebra", "apple"], map.keys().collect::<Vec<_>>());
+///assert_eq!(Some(&Value::Bool(true)), map.get("apple"));
+///
+///map.sort_keys();
+///
+///assert_eq!(vec!["apple", "zebra"], map.keys().collect::<Vec<_>>());
+///```
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    entries: Vec<(String, Value)>,
+    index: HashMap<String, usize>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.index.get(key).map(|&index| &self.entries[index].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.index.get(key).map(|&index| &mut self.entries[index].1)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    ///Add a member at the end, or replace the value of an existing member in its place
+    ///
+    ///Returns the value that was replaced.
+    pub fn insert(&mut self, key: String, value: Value) -> Option<Value> {
+        match self.index.get(&key) {
+            Some(&index) => Some(std::mem::replace(&mut self.entries[index].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    ///Remove a member, keeping the order of the rest
+    ///
+    ///Takes time linear in the number of members after it, which all move up by one.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let index = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+
+        for (key, _) in &self.entries[index..] {
+            if let Some(position) = self.index.get_mut(key) {
+                *position -= 1;
+            }
+        }
+
+        Some(value)
+    }
+
+    ///Sort the members by key
+    pub fn sort_keys(&mut self) {
+        self.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.reindex();
+    }
+
+    ///Members in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(key, _)| key.as_str())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    fn reindex(&mut self) {
+        for (position, (key, _)) in self.entries.iter().enumerate() {
+            if let Some(index) = self.index.get_mut(key) {
+                *index = position;
+            }
+        }
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl FromIterator<(String, Value)> for Map {
+    ///Later members with the same key replace the value of earlier ones, see [`Map::insert`]
+    fn from_iter<T: IntoIterator<Item = (String, Value)>>(iter: T) -> Self {
+        let mut map = Map::new();
+
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+
+        map
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, Value);
+    type IntoIter = std::vec::IntoIter<(String, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod map_tests {
+    use crate::parser::value::Value;
+
+    use super::Map;
+
+    fn map(keys: &[&str]) -> Map {
+        keys.iter()
+            .enumerate()
+            .map(|(index, key)| (key.to_string(), Value::String(index.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_insertion_order() {
+        let mut map = map(&["c", "a", "b"]);
+
+        assert_eq!(
+            Some(Value::String("1".to_string())),
+            map.insert("a".to_string(), Value::Null)
+        );
+        assert_eq!(None, map.insert("d".to_string(), Value::Null));
+
+        assert_eq!(vec!["c", "a", "b", "d"], map.keys().collect::<Vec<_>>());
+        assert_eq!(Some(&Value::Null), map.get("a"));
+        assert_eq!(4, map.len());
+    }
+
+    #[test]
+    fn remove_keeps_index() {
+        let mut map = map(&["c", "a", "b", "d"]);
+
+        assert_eq!(Some(Value::String("1".to_string())), map.remove("a"));
+        assert_eq!(None, map.remove("a"));
+
+        assert_eq!(vec!["c", "b", "d"], map.keys().collect::<Vec<_>>());
+        assert_eq!(Some(&Value::String("2".to_string())), map.get("b"));
+        assert_eq!(Some(&Value::String("3".to_string())), map.get("d"));
+        assert!(!map.contains_key("a"));
+    }
+
+    #[test]
+    fn sort_keys() {
+        let mut map = map(&["c", "a", "b"]);
+        map.sort_keys();
+
+        assert_eq!(vec!["a", "b", "c"], map.keys().collect::<Vec<_>>());
+        assert_eq!(Some(&Value::String("0".to_string())), map.get("c"));
+
+        if let Some(value) = map.get_mut("c") {
+            *value = Value::Bool(false);
+        }
+        assert_eq!(Some(&Value::Bool(false)), map.values().last());
+    }
+
+    #[test]
+    fn equality_ignores_order() {
+        let mut sorted = map(&["b", "a"]);
+        sorted.sort_keys();
+
+        assert_eq!(map(&["b", "a"]), sorted);
+        assert_ne!(map(&["a"]), map(&["a", "b"]));
+    }
+}
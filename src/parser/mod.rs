@@ -0,0 +1,7 @@
+pub mod duplicate_keys;
+pub mod map;
+#[allow(clippy::module_inception)]
+pub mod parser;
+pub mod parser_error;
+pub mod spanned;
+pub mod value;
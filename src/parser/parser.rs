@@ -0,0 +1,741 @@
+use std::collections::HashMap;
+
+use crate::{
+    diagnostic::{diagnostic::Diagnostic, severity::Severity},
+    scanner::{scanner::Scanner, scanner_config::ScannerConfig, scanner_error::ScannerError},
+    token::{
+        token::Token, token_literal::TokenLiteral, token_position::TokenPosition,
+        token_type::TokenType,
+    },
+};
+
+use super::{
+    duplicate_keys::DuplicateKeys,
+    map::Map,
+    parser_error::ParserError,
+    spanned::{Member, Spanned, SpannedValue},
+    value::Value,
+};
+
+///Tokens a value can start with
+const VALUE_START: [TokenType; 7] = [
+    TokenType::LeftBrace,
+    TokenType::LeftBracket,
+    TokenType::String,
+    TokenType::Number,
+    TokenType::True,
+    TokenType::False,
+    TokenType::Null,
+];
+
+///Build a [`Value`] tree, or a [`SpannedValue`] tree keeping positions, from the tokens of a
+///single JSON document
+///
+///The parser is a recursive descent over the tokens of a [`Scanner`], or of any other source of
+///tokens through [`Parser::from_tokens`]. Comment tokens are skipped. Trailing commas and
+///unquoted keys are accepted when the dialect in the config allows them, and keys used twice in
+///an object are handled as [`Parser::duplicate_keys`] says.
+///
+///# Examples
+///
+///```
+///# use jtool::parser::{parser::Parser, parser_error::ParserError, value::Value};
+///# use jtool::token::token_type::TokenType;
+///let value = Parser::new("[1, true, null]").parse().unwrap();
+///
+///assert_eq!(3, value.as_array().unwrap().len());
+///
+///let error = Parser::new("[1 2]").parse().unwrap_err();
+///
+///assert!(matches!(
+///    error,
+///    ParserError::UnexpectedToken(_, TokenType::Number, ref expected)
+///        if expected == &[TokenType::Comma, TokenType::RightBracket]
+///));
+///```
+#[derive(Debug)]
+pub struct Parser<I> {
+    pub config: ScannerConfig,
+    ///What to do with a key used twice in one object, an error by default
+    pub duplicate_keys: DuplicateKeys,
+    ///Warnings found while parsing, such as duplicate keys under [`DuplicateKeys::Warn`]
+    pub warnings: Vec<Diagnostic>,
+    tokens: I,
+    ///Position of the last token read, used for the end of input when the tokens run out
+    ///without an [`TokenType::Eof`]
+    last_position: TokenPosition,
+    ///Arrays and objects open around the token being parsed
+    depth: usize,
+}
+
+impl<'a> Parser<Scanner<'a>> {
+    pub fn new(source: &'a str) -> Self {
+        Self::with_config(source, ScannerConfig::default())
+    }
+
+    pub fn with_config(source: &'a str, config: ScannerConfig) -> Self {
+        Self::from_tokens(Scanner::with_config(source, config), config)
+    }
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = Result<Token, ScannerError>>,
+{
+    ///Parse tokens scanned with `config`
+    ///
+    ///The dialect of the config decides which extensions the grammar accepts and
+    ///[`Limits::max_depth`](crate::scanner::limits::Limits::max_depth) how deeply arrays and
+    ///objects may nest, the rest is up to whatever scanned the tokens.
+    pub fn from_tokens(tokens: I, config: ScannerConfig) -> Self {
+        Self {
+            config,
+            duplicate_keys: DuplicateKeys::default(),
+            warnings: vec![],
+            tokens,
+            last_position: TokenPosition::new(1, 1, 1, 1, 0, 0),
+            depth: 0,
+        }
+    }
+
+    ///Parse a single value followed by the end of input
+    pub fn parse(&mut self) -> Result<Value, ParserError> {
+        self.parse_document::<ValueBuilder>()
+    }
+
+    ///Parse a single value followed by the end of input, keeping the position of every node
+    ///
+    ///Input without any value fails with [`ScannerError::EmptyInput`], whatever the config's
+    ///`require_value` says.
+    pub fn parse_spanned(&mut self) -> Result<Spanned<SpannedValue>, ParserError> {
+        self.parse_document::<SpannedBuilder>()
+    }
+
+    fn parse_document<B: Builder>(&mut self) -> Result<B::Node, ParserError> {
+        let token = self.next_token()?;
+        if token.token_type == TokenType::Eof {
+            return Err(ScannerError::EmptyInput(token.token_position).into());
+        }
+
+        let value = self.parse_value::<B>(token, &VALUE_START)?;
+
+        let token = self.next_token()?;
+        match token.token_type {
+            TokenType::Eof => Ok(value),
+            _ => Err(unexpected(token, &[TokenType::Eof])),
+        }
+    }
+
+    ///Parse the value starting with `token`, reporting `expected` if it cannot start one
+    fn parse_value<B: Builder>(
+        &mut self,
+        token: Token,
+        expected: &[TokenType],
+    ) -> Result<B::Node, ParserError> {
+        match token.token_type {
+            TokenType::LeftBrace | TokenType::LeftBracket => self.parse_nested::<B>(token),
+            TokenType::String
+            | TokenType::Number
+            | TokenType::True
+            | TokenType::False
+            | TokenType::Null => Ok(B::literal(token.token_literal, token.token_position)),
+            _ => Err(unexpected(token, expected)),
+        }
+    }
+
+    ///Parse the array or object opened by `token`, one level deeper than the value around it
+    fn parse_nested<B: Builder>(&mut self, token: Token) -> Result<B::Node, ParserError> {
+        let max_depth = self.config.limits.max_depth;
+
+        if self.depth >= max_depth {
+            return Err(ParserError::NestingTooDeep(token.token_position, max_depth));
+        }
+
+        self.depth += 1;
+        let value = match token.token_type {
+            TokenType::LeftBrace => self.parse_object::<B>(token.token_position),
+            _ => self.parse_array::<B>(token.token_position),
+        };
+        self.depth -= 1;
+
+        value
+    }
+
+    ///Parse the members of an object after its `{` at `start`
+    fn parse_object<B: Builder>(&mut self, start: TokenPosition) -> Result<B::Node, ParserError> {
+        let mut members = B::Members::default();
+        //Index of the member kept for each key and where the key first appeared, for finding
+        //duplicates
+        let mut keys: HashMap<String, (usize, TokenPosition)> = HashMap::new();
+        let mut key_types = self.key_types();
+
+        let mut token = self.next_token()?;
+        if token.token_type == TokenType::RightBrace {
+            return Ok(B::object(members, start.to(&token.token_position)));
+        }
+        key_types.push(TokenType::RightBrace);
+
+        loop {
+            if !key_types.contains(&token.token_type) {
+                return Err(unexpected(token, &key_types));
+            }
+            let key = Spanned::new(String::from(token.token_literal), token.token_position);
+
+            let duplicate = keys.get(&key.node).copied();
+            if let Some((_, first)) = duplicate {
+                self.duplicate_key(&key, first)?;
+            }
+
+            let colon = self.next_token()?;
+            if colon.token_type != TokenType::Colon {
+                return Err(unexpected(colon, &[TokenType::Colon]));
+            }
+
+            let value_start = self.next_token()?;
+            let value = self.parse_value::<B>(value_start, &VALUE_START)?;
+
+            match duplicate {
+                Some(_) if self.duplicate_keys == DuplicateKeys::FirstWins => {}
+                Some((index, _)) => B::insert(&mut members, index, key, value),
+                None => {
+                    keys.insert(key.node.clone(), (keys.len(), key.position));
+                    B::insert(&mut members, keys.len() - 1, key, value);
+                }
+            }
+
+            let comma = self.next_token()?;
+            match comma.token_type {
+                TokenType::Comma => {}
+                TokenType::RightBrace => {
+                    return Ok(B::object(members, start.to(&comma.token_position)))
+                }
+                _ => {
+                    return Err(unexpected(
+                        comma,
+                        &[TokenType::Comma, TokenType::RightBrace],
+                    ))
+                }
+            }
+
+            token = self.next_token()?;
+            if token.token_type == TokenType::RightBrace {
+                self.trailing_comma(comma)?;
+                return Ok(B::object(members, start.to(&token.token_position)));
+            }
+            key_types = self.key_types();
+        }
+    }
+
+    ///Parse the elements of an array after its `[` at `start`
+    fn parse_array<B: Builder>(&mut self, start: TokenPosition) -> Result<B::Node, ParserError> {
+        let mut values = vec![];
+
+        let token = self.next_token()?;
+        if token.token_type == TokenType::RightBracket {
+            return Ok(B::array(values, start.to(&token.token_position)));
+        }
+
+        let mut expected = VALUE_START.to_vec();
+        expected.push(TokenType::RightBracket);
+        values.push(self.parse_value::<B>(token, &expected)?);
+
+        loop {
+            let comma = self.next_token()?;
+            match comma.token_type {
+                TokenType::Comma => {}
+                TokenType::RightBracket => {
+                    return Ok(B::array(values, start.to(&comma.token_position)))
+                }
+                _ => {
+                    return Err(unexpected(
+                        comma,
+                        &[TokenType::Comma, TokenType::RightBracket],
+                    ))
+                }
+            }
+
+            let token = self.next_token()?;
+            if token.token_type == TokenType::RightBracket {
+                self.trailing_comma(comma)?;
+                return Ok(B::array(values, start.to(&token.token_position)));
+            }
+            values.push(self.parse_value::<B>(token, &VALUE_START)?);
+        }
+    }
+
+    ///Report `key` being used again after `first` as [`Parser::duplicate_keys`] asks
+    fn duplicate_key(
+        &mut self,
+        key: &Spanned<String>,
+        first: TokenPosition,
+    ) -> Result<(), ParserError> {
+        let error = ParserError::DuplicateKey(key.position, key.node.clone(), first);
+
+        match self.duplicate_keys {
+            DuplicateKeys::Error => Err(error),
+            DuplicateKeys::Warn => {
+                let mut warning = Diagnostic::from(&error);
+                warning.severity = Severity::Warning;
+                self.warnings.push(warning);
+
+                Ok(())
+            }
+            DuplicateKeys::FirstWins | DuplicateKeys::LastWins => Ok(()),
+        }
+    }
+
+    ///Accept the array or object closed right after `comma` if the dialect allows it
+    fn trailing_comma(&self, comma: Token) -> Result<(), ParserError> {
+        match self.config.dialect.allows_trailing_commas() {
+            true => Ok(()),
+            false => Err(ParserError::TrailingComma(comma.token_position)),
+        }
+    }
+
+    ///Tokens an object key can be
+    fn key_types(&self) -> Vec<TokenType> {
+        match self.config.dialect.allows_identifiers() {
+            true => vec![TokenType::String, TokenType::Identifier],
+            false => vec![TokenType::String],
+        }
+    }
+
+    ///Read the next token that is not a comment
+    ///
+    ///Tokens that run out without an [`TokenType::Eof`] end as if they had one at the last
+    ///token's position.
+    fn next_token(&mut self) -> Result<Token, ParserError> {
+        loop {
+            match self.tokens.next() {
+                Some(Ok(token)) if token.token_type == TokenType::Comment => {}
+                Some(Ok(token)) => {
+                    self.last_position = token.token_position;
+                    return Ok(token);
+                }
+                Some(Err(error)) => return Err(error.into()),
+                None => {
+                    return Ok(Token::new(
+                        TokenType::Eof,
+                        TokenLiteral::String(String::new()),
+                        self.last_position,
+                    ))
+                }
+            }
+        }
+    }
+}
+
+///Assembles the nodes of the tree a [`Parser`] parses into
+///
+///[`Parser::parse`] builds [`Value`]s directly rather than dropping the positions of a
+///[`SpannedValue`] tree, which would allocate every node twice.
+trait Builder {
+    type Node;
+    type Members: Default;
+
+    ///The value of a string, number or keyword token
+    fn literal(literal: TokenLiteral, position: TokenPosition) -> Self::Node;
+
+    ///Add the member at `index`, replacing the member already there
+    fn insert(members: &mut Self::Members, index: usize, key: Spanned<String>, value: Self::Node);
+
+    fn object(members: Self::Members, position: TokenPosition) -> Self::Node;
+
+    fn array(values: Vec<Self::Node>, position: TokenPosition) -> Self::Node;
+}
+
+struct ValueBuilder;
+
+impl Builder for ValueBuilder {
+    type Node = Value;
+    type Members = Map;
+
+    fn literal(literal: TokenLiteral, _: TokenPosition) -> Value {
+        match literal {
+            TokenLiteral::String(value) => Value::String(value),
+            TokenLiteral::Number(value) => Value::Number(value),
+            TokenLiteral::Bool(value) => Value::Bool(value),
+            TokenLiteral::Null => Value::Null,
+        }
+    }
+
+    ///The map already replaces a key it has in place, so the index is not needed
+    fn insert(members: &mut Map, _: usize, key: Spanned<String>, value: Value) {
+        members.insert(key.node, value);
+    }
+
+    fn object(members: Map, _: TokenPosition) -> Value {
+        Value::Object(members)
+    }
+
+    fn array(values: Vec<Value>, _: TokenPosition) -> Value {
+        Value::Array(values)
+    }
+}
+
+struct SpannedBuilder;
+
+impl Builder for SpannedBuilder {
+    type Node = Spanned<SpannedValue>;
+    type Members = Vec<Member>;
+
+    fn literal(literal: TokenLiteral, position: TokenPosition) -> Self::Node {
+        let value = match literal {
+            TokenLiteral::String(value) => SpannedValue::String(value),
+            TokenLiteral::Number(value) => SpannedValue::Number(value),
+            TokenLiteral::Bool(value) => SpannedValue::Bool(value),
+            TokenLiteral::Null => SpannedValue::Null,
+        };
+
+        Spanned::new(value, position)
+    }
+
+    fn insert(members: &mut Vec<Member>, index: usize, key: Spanned<String>, value: Self::Node) {
+        let member = Member { key, value };
+
+        match members.get_mut(index) {
+            Some(existing) => *existing = member,
+            None => members.push(member),
+        }
+    }
+
+    fn object(members: Vec<Member>, position: TokenPosition) -> Self::Node {
+        Spanned::new(SpannedValue::Object(members), position)
+    }
+
+    fn array(values: Vec<Self::Node>, position: TokenPosition) -> Self::Node {
+        Spanned::new(SpannedValue::Array(values), position)
+    }
+}
+
+fn unexpected(token: Token, expected: &[TokenType]) -> ParserError {
+    ParserError::UnexpectedToken(token.token_position, token.token_type, expected.to_vec())
+}
+
+#[cfg(test)]
+mod parser_tests {
+    use crate::{
+        diagnostic::{diagnostic::Note, severity::Severity},
+        parser::{
+            duplicate_keys::DuplicateKeys, map::Map, parser_error::ParserError, value::Value,
+        },
+        scanner::{
+            dialect::Dialect, limits::Limits, scanner_config::ScannerConfig,
+            scanner_error::ScannerError,
+        },
+        token::{
+            number::Number, token::Token, token_literal::TokenLiteral,
+            token_position::TokenPosition, token_type::TokenType,
+        },
+    };
+
+    use super::{Parser, VALUE_START};
+
+    fn json5() -> ScannerConfig {
+        ScannerConfig {
+            dialect: Dialect::Json5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_literals() {
+        assert_eq!(Ok(Value::Null), Parser::new("null").parse());
+        assert_eq!(Ok(Value::Bool(true)), Parser::new(" true ").parse());
+        assert_eq!(
+            Ok(Value::Number(Number::new("-1.5e3"))),
+            Parser::new("-1.5e3").parse()
+        );
+        assert_eq!(
+            Ok(Value::String("a\nb".to_string())),
+            Parser::new(r#""a\nb""#).parse()
+        );
+    }
+
+    #[test]
+    fn parse_nested() {
+        let value = Parser::new(r#"{"b": [1, {"c": null}], "a": {}, "d": []}"#)
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            Value::Object(Map::from_iter([
+                ("a".to_string(), Value::Object(Map::new())),
+                (
+                    "b".to_string(),
+                    Value::Array(vec![
+                        Value::Number(Number::new("1")),
+                        Value::Object(Map::from_iter([("c".to_string(), Value::Null)])),
+                    ])
+                ),
+                ("d".to_string(), Value::Array(vec![])),
+            ])),
+            value
+        );
+        assert_eq!(r#"{"b":[1,{"c":null}],"a":{},"d":[]}"#, value.to_string());
+    }
+
+    #[test]
+    fn unexpected_tokens() {
+        let mut expected = VALUE_START.to_vec();
+        expected.push(TokenType::RightBracket);
+
+        assert_eq!(
+            Err(ParserError::UnexpectedToken(
+                TokenPosition::new(1, 1, 2, 3, 1, 2),
+                TokenType::Colon,
+                expected
+            )),
+            Parser::new("[:]").parse()
+        );
+        assert_eq!(
+            Err(ParserError::UnexpectedToken(
+                TokenPosition::new(1, 1, 6, 7, 5, 6),
+                TokenType::Number,
+                vec![TokenType::Colon]
+            )),
+            Parser::new(r#"{"a" 1}"#).parse()
+        );
+        assert_eq!(
+            Err(ParserError::UnexpectedToken(
+                TokenPosition::new(1, 1, 2, 3, 1, 2),
+                TokenType::Number,
+                vec![TokenType::String, TokenType::RightBrace]
+            )),
+            Parser::new("{1: 2}").parse()
+        );
+        assert_eq!(
+            Err(ParserError::UnexpectedToken(
+                TokenPosition::new(1, 1, 4, 5, 3, 4),
+                TokenType::Number,
+                vec![TokenType::Eof]
+            )),
+            Parser::new("[] 1").parse()
+        );
+        assert_eq!(
+            Err(ParserError::UnexpectedToken(
+                TokenPosition::new(1, 1, 1, 2, 0, 1),
+                TokenType::RightBrace,
+                VALUE_START.to_vec()
+            )),
+            Parser::new("}").parse()
+        );
+    }
+
+    #[test]
+    fn unexpected_end_of_input() {
+        let error = Parser::new("{\"a\": [1").parse().unwrap_err();
+
+        assert!(matches!(
+            error,
+            ParserError::UnexpectedToken(_, TokenType::Eof, ref expected)
+                if expected == &[TokenType::Comma, TokenType::RightBracket]
+        ));
+        assert_eq!(
+            Err(ParserError::Scanner(ScannerError::EmptyInput(
+                TokenPosition::new(1, 1, 1, 1, 0, 0)
+            ))),
+            Parser::new("").parse()
+        );
+        assert_eq!(
+            Err(ParserError::Scanner(ScannerError::EmptyInput(
+                TokenPosition::new(1, 1, 4, 4, 3, 3)
+            ))),
+            Parser::new("   ").parse()
+        );
+        assert_eq!(
+            Err(ParserError::Scanner(ScannerError::EmptyInput(
+                TokenPosition::new(1, 1, 1, 1, 0, 0)
+            ))),
+            Parser::from_tokens(std::iter::empty(), ScannerConfig::default()).parse()
+        );
+    }
+
+    #[test]
+    fn trailing_commas() {
+        assert_eq!(
+            Err(ParserError::TrailingComma(TokenPosition::new(
+                1, 1, 3, 4, 2, 3
+            ))),
+            Parser::new("[1,]").parse()
+        );
+        assert_eq!(
+            Err(ParserError::TrailingComma(TokenPosition::new(
+                1, 1, 8, 9, 7, 8
+            ))),
+            Parser::new(r#"{"a": 1,}"#).parse()
+        );
+        assert_eq!(
+            Ok(Value::Array(vec![Value::Number(Number::new("1"))])),
+            Parser::with_config("[1,]", json5()).parse()
+        );
+        assert!(Parser::with_config("[,]", json5()).parse().is_err());
+    }
+
+    #[test]
+    fn json5_extensions() {
+        let value = Parser::with_config("{unquoted: 'single', // note\n hex: 0x1F,}", json5())
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            Some("single"),
+            value.get("unquoted").and_then(Value::as_str)
+        );
+        assert_eq!(
+            Some("0x1F"),
+            value
+                .get("hex")
+                .and_then(Value::as_number)
+                .map(Number::as_str)
+        );
+        assert!(matches!(
+            Parser::new("{a: 1}").parse(),
+            Err(ParserError::Scanner(ScannerError::UnknownLiteral(..)))
+        ));
+    }
+
+    #[test]
+    fn scanner_errors() {
+        assert_eq!(
+            Err(ParserError::Scanner(ScannerError::UnterminatedString(
+                TokenPosition::new(1, 1, 2, 5, 1, 4)
+            ))),
+            Parser::new("[\"ab").parse()
+        );
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let config = ScannerConfig {
+            limits: Limits {
+                max_depth: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(Parser::with_config(r#"[{"a": 1}, []]"#, config)
+            .parse()
+            .is_ok());
+        assert_eq!(
+            Err(ParserError::NestingTooDeep(
+                TokenPosition::new(1, 1, 8, 9, 7, 8),
+                2
+            )),
+            Parser::with_config(r#"[{"a": [1]}]"#, config).parse()
+        );
+
+        let deep = "[".repeat(100_000);
+
+        assert!(matches!(
+            Parser::new(&deep).parse(),
+            Err(ParserError::NestingTooDeep(_, 128))
+        ));
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let source = r#"{"a": 1, "b": 2, "a": 3}"#;
+        let parse = |duplicate_keys| {
+            let mut parser = Parser::new(source);
+            parser.duplicate_keys = duplicate_keys;
+
+            (parser.parse(), parser.warnings)
+        };
+
+        let first = TokenPosition::new(1, 1, 2, 5, 1, 4);
+        let second = TokenPosition::new(1, 1, 18, 21, 17, 20);
+
+        assert_eq!(
+            (
+                Err(ParserError::DuplicateKey(second, "a".to_string(), first)),
+                vec![]
+            ),
+            parse(DuplicateKeys::Error)
+        );
+
+        let (first_wins, _) = parse(DuplicateKeys::FirstWins);
+        let (last_wins, _) = parse(DuplicateKeys::LastWins);
+
+        assert_eq!(r#"{"a":1,"b":2}"#, first_wins.unwrap().to_string());
+        assert_eq!(r#"{"a":3,"b":2}"#, last_wins.unwrap().to_string());
+
+        let (warn, warnings) = parse(DuplicateKeys::Warn);
+
+        assert_eq!(r#"{"a":3,"b":2}"#, warn.unwrap().to_string());
+        assert_eq!(1, warnings.len());
+        assert_eq!(Severity::Warning, warnings[0].severity);
+        assert_eq!(Some("J0104"), warnings[0].code);
+        assert_eq!(second, warnings[0].position);
+        assert_eq!(vec![Note::new("first used here", first)], warnings[0].notes);
+    }
+
+    #[test]
+    fn duplicate_keys_point_at_first_key() {
+        let source = r#"{"a": 1, "a": 2, "a": 3}"#;
+        let first = TokenPosition::new(1, 1, 2, 5, 1, 4);
+
+        let mut parser = Parser::new(source);
+        parser.duplicate_keys = DuplicateKeys::Warn;
+
+        assert_eq!(r#"{"a":3}"#, parser.parse().unwrap().to_string());
+        assert_eq!(
+            vec![
+                TokenPosition::new(1, 1, 10, 13, 9, 12),
+                TokenPosition::new(1, 1, 18, 21, 17, 20),
+            ],
+            parser
+                .warnings
+                .iter()
+                .map(|warning| warning.position)
+                .collect::<Vec<_>>()
+        );
+
+        for warning in &parser.warnings {
+            assert_eq!(vec![Note::new("first used here", first)], warning.notes);
+        }
+    }
+
+    #[test]
+    fn duplicate_keys_are_per_object() {
+        assert!(Parser::new(r#"[{"a": 1}, {"a": {"a": 2}}]"#)
+            .parse()
+            .is_ok());
+
+        let mut parser = Parser::with_config("{a: 1, 'a': 2}", json5());
+        assert!(matches!(
+            parser.parse(),
+            Err(ParserError::DuplicateKey(_, ref key, _)) if key == "a"
+        ));
+    }
+
+    #[test]
+    fn from_tokens() {
+        let tokens = vec![
+            Token::new(
+                TokenType::LeftBracket,
+                TokenLiteral::String("[".to_string()),
+                TokenPosition::new(1, 1, 1, 2, 0, 1),
+            ),
+            Token::new(
+                TokenType::True,
+                TokenLiteral::Bool(true),
+                TokenPosition::new(1, 1, 2, 6, 1, 5),
+            ),
+        ];
+
+        let error = Parser::from_tokens(tokens.into_iter().map(Ok), ScannerConfig::default())
+            .parse()
+            .unwrap_err();
+
+        assert_eq!(
+            ParserError::UnexpectedToken(
+                TokenPosition::new(1, 1, 2, 6, 1, 5),
+                TokenType::Eof,
+                vec![TokenType::Comma, TokenType::RightBracket]
+            ),
+            error
+        );
+    }
+}
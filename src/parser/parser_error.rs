@@ -0,0 +1,200 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    diagnostic::{diagnostic::Note, suggestion::Suggestion},
+    scanner::scanner_error::{limit_help, ScannerError},
+    token::{token_position::TokenPosition, token_type::TokenType},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum ParserError {
+    ///The tokens could not be scanned
+    Scanner(ScannerError),
+    ///A token that cannot appear here, with the tokens that could have
+    UnexpectedToken(TokenPosition, TokenType, Vec<TokenType>),
+    ///A comma after the last element of an array or object, in a dialect that does not allow it
+    TrailingComma(TokenPosition),
+    ///An array or object nested deeper than the limit, at its opening bracket
+    NestingTooDeep(TokenPosition, usize),
+    ///A key used twice in the same object, at the second use with the position of the first
+    DuplicateKey(TokenPosition, String, TokenPosition),
+}
+
+impl ParserError {
+    ///Where in the source the error was found
+    pub fn position(&self) -> &TokenPosition {
+        match self {
+            Self::Scanner(error) => error.position(),
+            Self::UnexpectedToken(position, ..)
+            | Self::TrailingComma(position)
+            | Self::NestingTooDeep(position, _)
+            | Self::DuplicateKey(position, ..) => position,
+        }
+    }
+
+    ///Stable code identifying the kind of error
+    ///
+    ///Parser errors use `J0100`-`J0199`, scanner errors keep their own code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Scanner(error) => error.code(),
+            Self::UnexpectedToken(..) => "J0101",
+            Self::TrailingComma(..) => "J0102",
+            Self::NestingTooDeep(..) => "J0103",
+            Self::DuplicateKey(..) => "J0104",
+        }
+    }
+
+    ///Describe the error without its position
+    pub fn message(&self) -> String {
+        match self {
+            Self::Scanner(error) => error.message(),
+            Self::UnexpectedToken(_, found, expected) => {
+                format!("Unexpected {}, expected {}", found, describe(expected))
+            }
+            Self::TrailingComma(_) => "Trailing comma".to_string(),
+            Self::NestingTooDeep(_, limit) => {
+                format!("Nesting deeper than the limit of {} levels", limit)
+            }
+            Self::DuplicateKey(_, key, _) => format!("Duplicate key [{}]", key),
+        }
+    }
+
+    ///Short description of what is wrong with the text under the error's span
+    pub fn label(&self) -> Option<String> {
+        match self {
+            Self::Scanner(error) => error.label(),
+            Self::UnexpectedToken(_, _, expected) => {
+                Some(format!("expected {}", describe(expected)))
+            }
+            Self::TrailingComma(_) => Some("not allowed after the last element".to_string()),
+            Self::NestingTooDeep(..) => Some("nested too deeply".to_string()),
+            Self::DuplicateKey(..) => Some("used again here".to_string()),
+        }
+    }
+
+    ///Notes on how to fix the error
+    pub fn help(&self) -> Vec<String> {
+        match self {
+            Self::Scanner(error) => error.help(),
+            Self::UnexpectedToken(..) => vec![],
+            Self::TrailingComma(_) => vec!["remove the comma".to_string()],
+            Self::NestingTooDeep(..) => vec![limit_help("max_depth")],
+            Self::DuplicateKey(..) => vec!["remove or rename one of the members".to_string()],
+        }
+    }
+
+    ///Other places in the source involved in the error
+    pub fn notes(&self) -> Vec<Note> {
+        match self {
+            Self::DuplicateKey(_, _, first) => vec![Note::new("first used here", *first)],
+            _ => vec![],
+        }
+    }
+
+    ///Fix proposed for the error, if one is known
+    pub fn suggestion(&self) -> Option<&Suggestion> {
+        match self {
+            Self::Scanner(error) => error.suggestion(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ScannerError> for ParserError {
+    fn from(error: ScannerError) -> Self {
+        Self::Scanner(error)
+    }
+}
+
+impl Display for ParserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Self::Scanner(error) = self {
+            return write!(f, "{}", error);
+        }
+
+        let TokenPosition {
+            line_start: line,
+            column_start,
+            column_end,
+            ..
+        } = self.position();
+
+        write!(
+            f,
+            "Error at [line:{}, between:{}-{}] {}",
+            line,
+            column_start,
+            column_end,
+            self.message()
+        )
+    }
+}
+
+impl Error for ParserError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Scanner(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+///List the expected tokens as `a, b or c`
+fn describe(expected: &[TokenType]) -> String {
+    let names: Vec<String> = expected.iter().map(TokenType::to_string).collect();
+
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => "nothing".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod parser_error_tests {
+    use crate::{
+        scanner::scanner_error::ScannerError,
+        token::{token_position::TokenPosition, token_type::TokenType},
+    };
+
+    use super::ParserError;
+
+    #[test]
+    fn unexpected_token() {
+        let error = ParserError::UnexpectedToken(
+            TokenPosition::new(1, 1, 5, 6, 4, 5),
+            TokenType::RightBrace,
+            vec![TokenType::String, TokenType::Number, TokenType::Null],
+        );
+
+        assert_eq!(
+            "Error at [line:1, between:5-6] Unexpected `}`, expected string, number or `null`",
+            error.to_string()
+        );
+        assert_eq!("J0101", error.code());
+    }
+
+    #[test]
+    fn trailing_comma() {
+        let error = ParserError::TrailingComma(TokenPosition::new(2, 2, 3, 4, 9, 10));
+
+        assert_eq!(
+            "Error at [line:2, between:3-4] Trailing comma",
+            error.to_string()
+        );
+        assert_eq!(vec!["remove the comma".to_string()], error.help());
+    }
+
+    #[test]
+    fn scanner_error() {
+        let scanner = ScannerError::UnterminatedString(TokenPosition::new(1, 1, 1, 3, 0, 2));
+        let error = ParserError::from(scanner);
+
+        assert_eq!(
+            "Error at [line:1, between:1-3] Unterminated string",
+            error.to_string()
+        );
+        assert_eq!("J0003", error.code());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::token::{number::Number, token_position::TokenPosition};
+
+use super::{map::Map, value::Value};
+
+///A node of the parsed tree together with the source it was parsed from
+#[derive(Debug, PartialEq, Clone)]
+pub struct Spanned<T> {
+    pub node: T,
+    ///Covers the whole node, from the opening to the closing bracket for arrays and objects
+    pub position: TokenPosition,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, position: TokenPosition) -> Self {
+        Self { node, position }
+    }
+}
+
+///A member of an object, with the positions of both its key and its value
+#[derive(Debug, PartialEq, Clone)]
+pub struct Member {
+    pub key: Spanned<String>,
+    pub value: Spanned<SpannedValue>,
+}
+
+///A parsed JSON document in which every value, key and element keeps its position
+///
+///Object members are kept in a `Vec` in the order they appear in the source.
+///
+///# Examples
+///
+///```
+///# use jtool::parser::parser::Parser;
+///let value = Parser::new("{\"a\": [1,\n  true]}").parse_spanned().unwrap();
+///
+///let element = &value.node.get("a").unwrap().node.as_array().unwrap()[1];
+///
+///assert_eq!(2, element.position.line_start);
+///assert_eq!(3, element.position.column_start);
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub enum SpannedValue {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Spanned<SpannedValue>>),
+    Object(Vec<Member>),
+}
+
+impl SpannedValue {
+    pub fn as_array(&self) -> Option<&Vec<Spanned<SpannedValue>>> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Vec<Member>> {
+        match self {
+            Self::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    ///Look up the member of an object with `key`
+    pub fn get(&self, key: &str) -> Option<&Spanned<SpannedValue>> {
+        self.as_object()?
+            .iter()
+            .find(|member| member.key.node == key)
+            .map(|member| &member.value)
+    }
+
+    ///Drop the positions
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Bool(value) => Value::Bool(value),
+            Self::Number(value) => Value::Number(value),
+            Self::String(value) => Value::String(value),
+            Self::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| value.node.into_value())
+                    .collect(),
+            ),
+            Self::Object(members) => Value::Object(
+                members
+                    .into_iter()
+                    .map(|member| (member.key.node, member.value.node.into_value()))
+                    .collect::<Map>(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod spanned_tests {
+    use crate::{
+        parser::{duplicate_keys::DuplicateKeys, parser::Parser, value::Value},
+        token::token_position::TokenPosition,
+    };
+
+    #[test]
+    fn spans_cover_nodes() {
+        let value = Parser::new("{\n  \"key\": [1, {}]\n}")
+            .parse_spanned()
+            .unwrap();
+
+        assert_eq!(TokenPosition::new(1, 3, 1, 2, 0, 20), value.position);
+
+        let member = &value.node.as_object().unwrap()[0];
+        assert_eq!(TokenPosition::new(2, 2, 3, 8, 4, 9), member.key.position);
+        assert_eq!(
+            TokenPosition::new(2, 2, 10, 17, 11, 18),
+            member.value.position
+        );
+
+        let elements = member.value.node.as_array().unwrap();
+        assert_eq!(
+            TokenPosition::new(2, 2, 11, 12, 12, 13),
+            elements[0].position
+        );
+        assert_eq!(
+            TokenPosition::new(2, 2, 14, 16, 15, 17),
+            elements[1].position
+        );
+    }
+
+    #[test]
+    fn members_keep_source_order() {
+        let mut parser = Parser::new(r#"{"b": 1, "a": 2, "b": 3}"#);
+        parser.duplicate_keys = DuplicateKeys::LastWins;
+
+        let value = parser.parse_spanned().unwrap().node;
+
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|member| member.key.node.as_str())
+            .collect();
+
+        assert_eq!(vec!["b", "a"], keys);
+        assert_eq!(
+            Some(&TokenPosition::new(1, 1, 23, 24, 22, 23)),
+            value.get("b").map(|value| &value.position)
+        );
+        assert_eq!(r#"{"b":3,"a":2}"#, value.into_value().to_string());
+        assert_eq!(
+            Ok(Value::Null),
+            Parser::new("null")
+                .parse_spanned()
+                .map(|value| value.node.into_value())
+        );
+    }
+}
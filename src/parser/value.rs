@@ -0,0 +1,178 @@
+use std::fmt::Display;
+
+use crate::token::number::Number;
+
+use super::map::Map;
+
+///A parsed JSON document
+///
+///Object members keep the order they had in the source, see [`Map`]. Numbers keep their source
+///text, see [`Number`].
+///
+///# Examples
+///
+///```
+///# use jtool::parser::{parser::Parser, value::Value};
+///let value = Parser::new(r#"{"name": "jtool", "tags": ["json"], "stable": false}"#)
+///    .parse()
+///    .unwrap();
+///
+///assert_eq!(Some("jtool"), value.get("name").and_then(Value::as_str));
+///assert_eq!(Some(false), value.get("stable").and_then(Value::as_bool));
+///assert_eq!(r#"{"name":"jtool","tags":["json"],"stable":false}"#, value.to_string());
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+}
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Self::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    ///Look up a member of an object
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.get(key)
+    }
+}
+
+///Write the value as compact JSON
+///
+///Numbers are written as they appeared in the source, so a value parsed from JSON5 may contain
+///numbers such as `0x1F` or `Infinity` that strict JSON does not allow.
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Number(value) => write!(f, "{}", value),
+            Self::String(value) => write_escaped(f, value),
+            Self::Array(values) => {
+                write!(f, "[")?;
+
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+
+                write!(f, "]")
+            }
+            Self::Object(members) => {
+                write!(f, "{{")?;
+
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+///Write `text` as a quoted JSON string, escaping quotes, backslashes and control characters
+pub(crate) fn write_escaped(out: &mut impl std::fmt::Write, text: &str) -> std::fmt::Result {
+    out.write_char('"')?;
+
+    for char in text.chars() {
+        match char {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            _ if char.is_control() => write!(out, "\\u{:04x}", u32::from(char))?,
+            _ => out.write_char(char)?,
+        }
+    }
+
+    out.write_char('"')
+}
+
+#[cfg(test)]
+mod value_tests {
+    use crate::{parser::map::Map, token::number::Number};
+
+    use super::Value;
+
+    #[test]
+    fn accessors() {
+        let value = Value::Object(Map::from_iter([
+            ("a".to_string(), Value::Number(Number::new("1.50"))),
+            ("b".to_string(), Value::Array(vec![Value::Null])),
+        ]));
+
+        assert_eq!(
+            Some("1.50"),
+            value
+                .get("a")
+                .and_then(Value::as_number)
+                .map(Number::as_str)
+        );
+        assert_eq!(
+            Some(1),
+            value.get("b").and_then(Value::as_array).map(Vec::len)
+        );
+        assert_eq!(None, value.get("c"));
+        assert_eq!(None, value.as_str());
+        assert!(Value::Null.is_null());
+    }
+
+    #[test]
+    fn display_escapes_strings() {
+        let value = Value::Array(vec![
+            Value::String("a \"quote\"\n\u{1}é".to_string()),
+            Value::Bool(true),
+            Value::Object(Map::new()),
+        ]);
+
+        assert_eq!(r#"["a \"quote\"\n\u0001é",true,{}]"#, value.to_string());
+    }
+}
@@ -0,0 +1,107 @@
+///The flavour of JSON a [`Scanner`](super::scanner::Scanner) accepts
+///
+///Every dialect other than [`Dialect::Json`] is a superset of it, so a strict document scans the
+///same way in all of them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Dialect {
+    ///Strict JSON as defined by RFC 8259
+    #[default]
+    Json,
+    ///JSON5 as defined by <https://spec.json5.org>
+    Json5,
+    ///JSON with comments and trailing commas, as used by VS Code settings and `tsconfig.json`
+    Jsonc,
+}
+
+impl Dialect {
+    ///`//` line comments and `/* */` block comments, scanned as
+    ///[`TokenType::Comment`](crate::token::token_type::TokenType::Comment)
+    pub fn allows_comments(self) -> bool {
+        matches!(self, Self::Json5 | Self::Jsonc)
+    }
+
+    ///A comma after the last element of an array or object
+    ///
+    ///Commas are scanned the same way in every dialect, this is for whatever consumes the tokens.
+    pub fn allows_trailing_commas(self) -> bool {
+        matches!(self, Self::Json5 | Self::Jsonc)
+    }
+
+    ///Unquoted object keys, scanned as identifiers
+    pub fn allows_identifiers(self) -> bool {
+        matches!(self, Self::Json5)
+    }
+
+    ///Strings delimited by `'` as well as `"`
+    pub fn allows_single_quotes(self) -> bool {
+        matches!(self, Self::Json5)
+    }
+
+    ///Hexadecimal numbers, `Infinity`, `NaN`, a leading `+` and a leading or trailing `.`
+    pub fn allows_extended_numbers(self) -> bool {
+        matches!(self, Self::Json5)
+    }
+
+    ///Extra escapes such as `\x41`, `\v` and `\0`, and line continuations in strings
+    pub fn allows_extended_escapes(self) -> bool {
+        matches!(self, Self::Json5)
+    }
+
+    ///Control characters other than line endings left unescaped inside strings
+    pub fn allows_raw_control_characters(self) -> bool {
+        matches!(self, Self::Json5)
+    }
+
+    ///Unicode space separators, vertical tab, form feed and the byte order mark as whitespace
+    pub fn allows_extended_whitespace(self) -> bool {
+        matches!(self, Self::Json5)
+    }
+}
+
+#[cfg(test)]
+mod dialect_tests {
+    use super::Dialect;
+
+    #[test]
+    fn json_is_strict() {
+        let json = Dialect::default();
+
+        assert_eq!(Dialect::Json, json);
+        assert!(!json.allows_comments());
+        assert!(!json.allows_trailing_commas());
+        assert!(!json.allows_identifiers());
+        assert!(!json.allows_single_quotes());
+        assert!(!json.allows_extended_numbers());
+        assert!(!json.allows_extended_escapes());
+        assert!(!json.allows_extended_whitespace());
+        assert!(!json.allows_raw_control_characters());
+    }
+
+    #[test]
+    fn json5_extensions() {
+        let json5 = Dialect::Json5;
+
+        assert!(json5.allows_comments());
+        assert!(json5.allows_trailing_commas());
+        assert!(json5.allows_identifiers());
+        assert!(json5.allows_single_quotes());
+        assert!(json5.allows_extended_numbers());
+        assert!(json5.allows_extended_escapes());
+        assert!(json5.allows_extended_whitespace());
+        assert!(json5.allows_raw_control_characters());
+    }
+
+    #[test]
+    fn jsonc_only_adds_comments_and_trailing_commas() {
+        let jsonc = Dialect::Jsonc;
+
+        assert!(jsonc.allows_comments());
+        assert!(jsonc.allows_trailing_commas());
+        assert!(!jsonc.allows_identifiers());
+        assert!(!jsonc.allows_single_quotes());
+        assert!(!jsonc.allows_extended_numbers());
+        assert!(!jsonc.allows_extended_escapes());
+        assert!(!jsonc.allows_extended_whitespace());
+        assert!(!jsonc.allows_raw_control_characters());
+    }
+}
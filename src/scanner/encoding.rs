@@ -0,0 +1,317 @@
+use std::{borrow::Cow, fmt::Display};
+
+use crate::{source_map::source_map::SourceMap, token::token_position::TokenPosition};
+
+use super::scanner_error::ScannerError;
+
+///Character encodings a JSON document can be read from
+///
+///RFC 8259 only allows UTF-8 between systems, but UTF-16 and UTF-32 documents, often starting with
+///a byte order mark, still turn up from Windows tooling.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Encoding {
+    ///Find the encoding of a document from its first bytes
+    ///
+    ///A byte order mark decides the encoding. Without one the pattern of zero bytes in the first
+    ///four is used, as described in RFC 4627, which works because the first two characters of a
+    ///JSON document are always ASCII. Anything else is UTF-8.
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0x00, 0x00, 0xfe, 0xff, ..] => Self::Utf32Be,
+            [0xff, 0xfe, 0x00, 0x00, ..] => Self::Utf32Le,
+            [0xef, 0xbb, 0xbf, ..] => Self::Utf8,
+            [0xfe, 0xff, ..] => Self::Utf16Be,
+            [0xff, 0xfe, ..] => Self::Utf16Le,
+            [0x00, 0x00, 0x00, _, ..] => Self::Utf32Be,
+            [_, 0x00, 0x00, 0x00, ..] => Self::Utf32Le,
+            [0x00, _, ..] => Self::Utf16Be,
+            [_, 0x00, ..] => Self::Utf16Le,
+            _ => Self::Utf8,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Utf32Le => "UTF-32LE",
+            Self::Utf32Be => "UTF-32BE",
+        }
+    }
+
+    ///Transcode the complete characters at the start of `bytes` onto the end of `text`
+    ///
+    ///Returns how many bytes were decoded, leaving the bytes of an unfinished last character for
+    ///when more input arrives. When an invalid character is found everything before it is decoded
+    ///and the number of bytes before it is returned as the error.
+    pub(crate) fn decode_into(&self, bytes: &[u8], text: &mut String) -> Result<usize, usize> {
+        match self {
+            Self::Utf8 => match std::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    Ok(bytes.len())
+                }
+                Err(error) => {
+                    let valid = error.valid_up_to();
+                    text.push_str(std::str::from_utf8(&bytes[..valid]).unwrap());
+
+                    match error.error_len() {
+                        Some(_) => Err(valid),
+                        None => Ok(valid),
+                    }
+                }
+            },
+            Self::Utf16Le | Self::Utf16Be => {
+                let mut index = 0;
+
+                while let Some(unit) = self.unit(bytes, index) {
+                    let (char, len) = match unit {
+                        0xd800..=0xdbff => match self.unit(bytes, index + 2) {
+                            Some(low @ 0xdc00..=0xdfff) => {
+                                let scalar = 0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00);
+                                (char::from_u32(scalar), 4)
+                            }
+                            Some(_) => (None, 4),
+                            None => break,
+                        },
+                        _ => (char::from_u32(unit), 2),
+                    };
+
+                    text.push(char.ok_or(index)?);
+                    index += len;
+                }
+
+                Ok(index)
+            }
+            Self::Utf32Le | Self::Utf32Be => {
+                let mut index = 0;
+
+                while let Some(unit) = self.unit(bytes, index) {
+                    text.push(char::from_u32(unit).ok_or(index)?);
+                    index += 4;
+                }
+
+                Ok(index)
+            }
+        }
+    }
+
+    ///Read the UTF-16 or UTF-32 code unit at `index` if all of its bytes are there
+    fn unit(&self, bytes: &[u8], index: usize) -> Option<u32> {
+        match self {
+            Self::Utf8 => bytes.get(index).copied().map(u32::from),
+            Self::Utf16Le => Some(u32::from(u16::from_le_bytes(
+                bytes.get(index..index + 2)?.try_into().ok()?,
+            ))),
+            Self::Utf16Be => Some(u32::from(u16::from_be_bytes(
+                bytes.get(index..index + 2)?.try_into().ok()?,
+            ))),
+            Self::Utf32Le => Some(u32::from_le_bytes(
+                bytes.get(index..index + 4)?.try_into().ok()?,
+            )),
+            Self::Utf32Be => Some(u32::from_be_bytes(
+                bytes.get(index..index + 4)?.try_into().ok()?,
+            )),
+        }
+    }
+
+    ///The error for input that is not valid in this encoding, found at `position`
+    pub(crate) fn error(&self, position: TokenPosition) -> ScannerError {
+        match self {
+            Self::Utf8 => ScannerError::InvalidUtf8(position),
+            _ => ScannerError::InvalidEncoding(position, *self),
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+///Decode a whole document into text the [`Scanner`](super::scanner::Scanner) can read, detecting
+///its encoding with [`Encoding::detect`]
+///
+///UTF-8 input is borrowed as is and other encodings are transcoded to UTF-8. A byte order mark is
+///kept as U+FEFF, which the scanner skips, so the offsets of tokens scanned from UTF-8 input are
+///byte offsets into the input.
+///
+///Input that is not valid in its encoding is reported with the position of the first invalid
+///byte, counted in the decoded text.
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::{encoding::decode, scanner::Scanner};
+///let utf16: Vec<u8> = [0xff, 0xfe]
+///    .into_iter()
+///    .chain("[1]".encode_utf16().flat_map(u16::to_le_bytes))
+///    .collect();
+///
+///let source = decode(&utf16).unwrap();
+///
+///assert_eq!("\u{feff}[1]", source);
+///assert_eq!(4, Scanner::new(&source).scan().unwrap().len());
+///```
+pub fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, ScannerError> {
+    let encoding = Encoding::detect(bytes);
+
+    if let (Encoding::Utf8, Ok(text)) = (encoding, std::str::from_utf8(bytes)) {
+        return Ok(Cow::Borrowed(text));
+    }
+
+    let mut text = String::with_capacity(bytes.len());
+
+    match encoding.decode_into(bytes, &mut text) {
+        Ok(decoded) if decoded == bytes.len() => Ok(Cow::Owned(text)),
+        _ => Err(encoding.error(end_position(&text))),
+    }
+}
+
+///Position of the character just past the end of `text`
+fn end_position(text: &str) -> TokenPosition {
+    let location = SourceMap::new(text).location(text.len()).unwrap();
+
+    TokenPosition::new(
+        location.line,
+        location.line,
+        location.column,
+        location.column + 1,
+        location.offset,
+        location.offset + 1,
+    )
+}
+
+#[cfg(test)]
+mod encoding_tests {
+    use crate::{
+        scanner::{scanner::Scanner, scanner_error::ScannerError},
+        token::token_position::TokenPosition,
+    };
+
+    use super::{decode, Encoding};
+
+    fn utf16(text: &str, little_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|unit| match little_endian {
+                true => unit.to_le_bytes(),
+                false => unit.to_be_bytes(),
+            })
+            .collect()
+    }
+
+    fn utf32(text: &str, little_endian: bool) -> Vec<u8> {
+        text.chars()
+            .flat_map(|char| match little_endian {
+                true => u32::from(char).to_le_bytes(),
+                false => u32::from(char).to_be_bytes(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detect_from_bom() {
+        assert_eq!(Encoding::Utf8, Encoding::detect(b"\xef\xbb\xbf[]"));
+        assert_eq!(Encoding::Utf16Le, Encoding::detect(b"\xff\xfe[\x00"));
+        assert_eq!(Encoding::Utf16Be, Encoding::detect(b"\xfe\xff\x00["));
+        assert_eq!(Encoding::Utf32Le, Encoding::detect(b"\xff\xfe\x00\x00"));
+        assert_eq!(Encoding::Utf32Be, Encoding::detect(b"\x00\x00\xfe\xff"));
+    }
+
+    #[test]
+    fn detect_from_null_bytes() {
+        assert_eq!(Encoding::Utf8, Encoding::detect(b"[1]"));
+        assert_eq!(Encoding::Utf8, Encoding::detect(b""));
+        assert_eq!(Encoding::Utf16Le, Encoding::detect(&utf16("[1]", true)));
+        assert_eq!(Encoding::Utf16Be, Encoding::detect(&utf16("[1]", false)));
+        assert_eq!(Encoding::Utf32Le, Encoding::detect(&utf32("[1]", true)));
+        assert_eq!(Encoding::Utf32Be, Encoding::detect(&utf32("[1]", false)));
+        assert_eq!(Encoding::Utf16Le, Encoding::detect(&utf16("1", true)));
+    }
+
+    #[test]
+    fn decode_every_encoding() {
+        let text = "{\"naïve\": [\"👍\", 1]}";
+
+        let inputs = [
+            text.as_bytes().to_vec(),
+            utf16(text, true),
+            utf16(text, false),
+            utf32(text, true),
+            utf32(text, false),
+        ];
+
+        for input in inputs {
+            assert_eq!(text, decode(&input).unwrap());
+            assert_eq!(
+                Scanner::new(text).scan(),
+                Scanner::new(&decode(&input).unwrap()).scan()
+            );
+        }
+
+        assert_eq!("\u{feff}[]", decode(&utf16("\u{feff}[]", false)).unwrap());
+    }
+
+    #[test]
+    fn decode_invalid_utf8() {
+        assert_eq!(
+            Err(ScannerError::InvalidUtf8(TokenPosition::new(
+                2, 2, 4, 5, 8, 9
+            ))),
+            decode(b"[1,\n \"\xc3\xa9\xff\"]")
+        );
+        assert_eq!(
+            Err(ScannerError::InvalidUtf8(TokenPosition::new(
+                1, 1, 3, 4, 2, 3
+            ))),
+            decode(b"[\"\xf0\x9f\x98")
+        );
+    }
+
+    #[test]
+    fn decode_invalid_utf16_and_utf32() {
+        let mut unpaired = utf16("[\"", true);
+        unpaired.extend_from_slice(&[0x00, 0xd8, 0x41, 0x00]);
+
+        assert_eq!(
+            Err(ScannerError::InvalidEncoding(
+                TokenPosition::new(1, 1, 3, 4, 2, 3),
+                Encoding::Utf16Le
+            )),
+            decode(&unpaired)
+        );
+
+        let mut truncated = utf16("[1]", false);
+        truncated.pop();
+
+        assert_eq!(
+            Err(ScannerError::InvalidEncoding(
+                TokenPosition::new(1, 1, 3, 4, 2, 3),
+                Encoding::Utf16Be
+            )),
+            decode(&truncated)
+        );
+
+        let mut out_of_range = utf32("[", true);
+        out_of_range.extend_from_slice(&0x110000u32.to_le_bytes());
+
+        assert_eq!(
+            Err(ScannerError::InvalidEncoding(
+                TokenPosition::new(1, 1, 2, 3, 1, 2),
+                Encoding::Utf32Le
+            )),
+            decode(&out_of_range)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+///Bounds on the input a [`Scanner`](super::scanner::Scanner) and
+///[`Parser`](crate::parser::parser::Parser) accept, to protect against untrusted documents
+///
+///The scanner enforces every limit except the nesting depth, which only the parser can see, and
+///stops at the first limit exceeded even when recovering from errors. The defaults are generous
+///for hand written and generated documents alike, use [`Limits::unlimited`] for trusted input.
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::{
+///#     limits::Limits, scanner::Scanner, scanner_config::ScannerConfig,
+///#     scanner_error::ScannerError,
+///# };
+///let config = ScannerConfig {
+///    limits: Limits {
+///        max_string_length: 4,
+///        ..Default::default()
+///    },
+///    ..Default::default()
+///};
+///
+///assert!(matches!(
+///    Scanner::with_config(r#"["abcde"]"#, config).scan(),
+///    Err(ScannerError::StringTooLong(_, 4))
+///));
+///```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Limits {
+    ///Deepest nesting of arrays and objects, a scalar at the top level is depth 0
+    pub max_depth: usize,
+    ///Longest string or identifier in bytes, after escape sequences are decoded
+    pub max_string_length: usize,
+    ///Longest number in bytes of source text
+    pub max_number_length: usize,
+    ///Longest bare word in bytes, such as `true` or a misspelled literal
+    pub max_literal_length: usize,
+    ///Largest document in bytes of UTF-8 text
+    pub max_document_size: usize,
+    ///Most tokens in a document, not counting the end of input
+    pub max_tokens: usize,
+}
+
+impl Limits {
+    ///No limits at all, for input that is trusted
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_string_length: usize::MAX,
+            max_number_length: usize::MAX,
+            max_literal_length: usize::MAX,
+            max_document_size: usize::MAX,
+            max_tokens: usize::MAX,
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_string_length: 16 * 1024 * 1024,
+            max_number_length: 1024,
+            max_literal_length: 1024,
+            max_document_size: 256 * 1024 * 1024,
+            max_tokens: 64 * 1024 * 1024,
+        }
+    }
+}
@@ -1,2 +1,9 @@
+pub mod dialect;
+pub mod encoding;
+pub mod limits;
+#[allow(clippy::module_inception)]
 pub mod scanner;
+pub mod scanner_config;
 pub mod scanner_error;
+pub mod stream_scanner;
+pub mod structural_index;
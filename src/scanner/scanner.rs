@@ -1,21 +1,46 @@
-use std::usize;
+use std::borrow::Cow;
 
+use crate::diagnostic::suggestion::{edit_distance, Applicability, Suggestion};
+use crate::source_map::{
+    column_unit::{ColumnCounter, ColumnUnit},
+    unicode,
+};
 use crate::token::{
-    token::Token, token_literal::TokenLiteral, token_position::TokenPosition, token_type::TokenType,
+    borrowed_literal::BorrowedLiteral,
+    borrowed_token::BorrowedToken,
+    token::Token,
+    token_position::TokenPosition,
+    token_type::TokenType,
+    trivia::{TokenTrivia, Trivia, TriviaKind},
+};
+
+use super::{
+    scanner_config::ScannerConfig, scanner_error::ScannerError, structural_index::StructuralIndex,
 };
 
-use super::scanner_error::ScannerError;
+///Spellings of literals in other languages and formats, and the literal they mean
+const FOREIGN_LITERALS: [(&str, &str); 4] = [
+    ("none", "null"),
+    ("nil", "null"),
+    ("undefined", "null"),
+    ("inf", "Infinity"),
+];
 
 ///Iterate over a string and group characters into tokens
 ///
 ///# Examples
 ///
 ///```
-///let mut scanner = Scanner::new("[ true, false ]");
-///let res: Result<Option<Token>, ScannerError> = scanner.scan();
+///# use jtool::scanner::{scanner::Scanner, scanner_error::ScannerError};
+///# use jtool::token::token::Token;
+///let scanner = Scanner::new("[ true, false ]");
+///let res: Result<Vec<Token>, ScannerError> = scanner.scan();
 ///println!("{:#?}", res);
+///```
 ///
-/// // Output:
+///Output:
+///
+///```text
 ///Ok(
 ///    [
 ///        Token {
@@ -24,11 +49,14 @@ use super::scanner_error::ScannerError;
 ///                "[",
 ///            ),
 ///            token_position: TokenPosition {
-///                line: 1,
+///                line_start: 1,
+///                line_end: 1,
 ///                column_start: 1,
 ///                column_end: 2,
-///                span: 1,
+///                offset_start: 0,
+///                offset_end: 1,
 ///            },
+///            trivia: None,
 ///        },
 ///        Token {
 ///            token_type: True,
@@ -36,11 +64,14 @@ use super::scanner_error::ScannerError;
 ///                true,
 ///            ),
 ///            token_position: TokenPosition {
-///                line: 1,
+///                line_start: 1,
+///                line_end: 1,
 ///                column_start: 3,
 ///                column_end: 7,
-///                span: 4,
+///                offset_start: 2,
+///                offset_end: 6,
 ///            },
+///            trivia: None,
 ///        },
 ///        Token {
 ///            token_type: Comma,
@@ -48,11 +79,14 @@ use super::scanner_error::ScannerError;
 ///                ",",
 ///            ),
 ///            token_position: TokenPosition {
-///                line: 1,
+///                line_start: 1,
+///                line_end: 1,
 ///                column_start: 7,
 ///                column_end: 8,
-///                span: 1,
+///                offset_start: 6,
+///                offset_end: 7,
 ///            },
+///            trivia: None,
 ///        },
 ///        Token {
 ///            token_type: False,
@@ -60,11 +94,14 @@ use super::scanner_error::ScannerError;
 ///                false,
 ///            ),
 ///            token_position: TokenPosition {
-///                line: 1,
+///                line_start: 1,
+///                line_end: 1,
 ///                column_start: 9,
 ///                column_end: 14,
-///                span: 5,
+///                offset_start: 8,
+///                offset_end: 13,
 ///            },
+///            trivia: None,
 ///        },
 ///        Token {
 ///            token_type: RightBracket,
@@ -72,99 +109,256 @@ use super::scanner_error::ScannerError;
 ///                "]",
 ///            ),
 ///            token_position: TokenPosition {
-///                line: 1,
+///                line_start: 1,
+///                line_end: 1,
 ///                column_start: 15,
 ///                column_end: 16,
-///                span: 1,
+///                offset_start: 14,
+///                offset_end: 15,
+///            },
+///            trivia: None,
+///        },
+///        Token {
+///            token_type: Eof,
+///            token_literal: String(
+///                "",
+///            ),
+///            token_position: TokenPosition {
+///                line_start: 1,
+///                line_end: 1,
+///                column_start: 16,
+///                column_end: 16,
+///                offset_start: 15,
+///                offset_end: 15,
 ///            },
+///            trivia: None,
 ///        },
 ///    ],
 ///)
 ///
 ///```
+///
+///`start` and `current` are byte offsets into `source`, so the cursor only ever moves forward
+///and every character is decoded once, keeping scanning linear in the size of the input.
+///`base_offset` is added to every reported offset for when `source` is a slice of a larger
+///document.
 #[derive(Debug)]
-pub struct Scanner {
-    pub source: String,
+pub struct Scanner<'a> {
+    pub source: &'a str,
     pub start: usize,
     pub current: usize,
-    pub line: i32,
-    pub column_start: i32,
-    pub column_end: i32,
+    pub base_offset: usize,
+    pub line_start: usize,
+    pub line: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub config: ScannerConfig,
+    pub(crate) columns: ColumnCounter,
+    pub(crate) exhausted: bool,
+    pub(crate) empty: bool,
+    ///Tokens scanned so far, checked against [`Limits::max_tokens`](super::limits::Limits)
+    pub(crate) token_count: usize,
+    done: bool,
+    index: Option<StructuralIndex>,
+    index_hint: usize,
 }
 
-impl Scanner {
-    pub fn new(source: &str) -> Self {
+impl<'a> Scanner<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self::with_config(source, ScannerConfig::default())
+    }
+
+    pub fn with_config(source: &'a str, config: ScannerConfig) -> Self {
         Self {
-            source: String::from(source),
+            source,
             start: 0,
             current: 0,
+            base_offset: 0,
+            line_start: 1,
             line: 1,
             column_start: 1,
             column_end: 1,
+            config,
+            columns: ColumnCounter::new(),
+            exhausted: false,
+            empty: true,
+            token_count: 0,
+            done: false,
+            index: None,
+            index_hint: 0,
+        }
+    }
+
+    ///Build a [`StructuralIndex`] of the source first and use it to jump over whitespace and
+    ///take strings without escapes, numbers and literals in one step
+    ///
+    ///Produces exactly the same tokens and errors as [`Scanner::with_config`], only faster on
+    ///large documents. Every jump is checked against the source, so input the index gets wrong
+    ///is scanned one character at a time as usual.
+    ///
+    ///# Examples
+    ///
+    ///```
+    ///# use jtool::scanner::{scanner::Scanner, scanner_config::ScannerConfig};
+    ///let source = r#"{"name": "jtool", "tags": ["json", "lexer"]}"#;
+    ///
+    ///assert_eq!(
+    ///    Scanner::new(source).scan(),
+    ///    Scanner::indexed(source, ScannerConfig::default()).scan()
+    ///);
+    ///```
+    pub fn indexed(source: &'a str, config: ScannerConfig) -> Self {
+        Self {
+            index: Some(StructuralIndex::new(source.as_bytes())),
+            ..Self::with_config(source, config)
         }
     }
 
-    pub fn scan(&mut self) -> Result<Vec<Token>, ScannerError> {
-        let mut tokens: Vec<Token> = vec![];
+    ///Scan the whole source, stopping at the first error
+    ///
+    ///A convenience over the [`Iterator`] implementation for when every token is needed up front.
+    ///It takes the scanner by value, which also keeps it from being shadowed by [`Iterator::scan`].
+    pub fn scan(self) -> Result<Vec<Token>, ScannerError> {
+        self.collect()
+    }
+
+    ///Scan the whole source into tokens borrowing from it, stopping at the first error
+    pub fn scan_borrowed(mut self) -> Result<Vec<BorrowedToken<'a>>, ScannerError> {
+        std::iter::from_fn(|| self.next_borrowed()).collect()
+    }
+
+    ///Scan the next token without copying it out of the source
+    ///
+    ///This is what the [`Iterator`] implementation is built on, it differs only in returning a
+    ///[`BorrowedToken`] instead of an owned [`Token`].
+    ///
+    ///The last token is always [`TokenType::Eof`], positioned at the end of the input, so a
+    ///consumer can tell the input was read to the end.
+    ///
+    ///With [`ScannerConfig::trivia`] set the whitespace and comments before a token are kept as
+    ///its leading trivia, and those after it up to the end of the line as its trailing trivia.
+    pub fn next_borrowed(&mut self) -> Option<Result<BorrowedToken<'a>, ScannerError>> {
+        if self.done {
+            return None;
+        }
+
+        if let Some(res) = self.next_indexed() {
+            return Some(res);
+        }
+
+        let mut leading = vec![];
+
+        loop {
+            if !self.config.trivia {
+                self.skip_indexed_whitespace();
+            }
+
+            if self.peek().is_none() {
+                break;
+            }
 
-        while self.peek().is_some() {
             self.start = self.current;
+            self.line_start = self.line;
 
-            if let Some(token) = self.eval()? {
-                tokens.push(token);
-            };
+            let res = self
+                .eval()
+                .and_then(|token| self.check_limits(token.as_ref()).map(|_| token));
+
+            match res {
+                Ok(Some(token)) if self.config.trivia && token.token_type == TokenType::Comment => {
+                    self.push_trivia(&mut leading, TriviaKind::Comment);
+                }
+                Ok(Some(token)) => {
+                    self.empty = false;
+                    return Some(Ok(self.attach_trivia(token, leading)));
+                }
+                Ok(None) => {
+                    if self.config.trivia {
+                        let kind = match &self.source[self.start..self.current] {
+                            "\n" | "\r" => TriviaKind::Newline,
+                            _ => TriviaKind::Whitespace,
+                        };
+
+                        self.push_trivia(&mut leading, kind);
+                    }
+                }
+                Err(error) => {
+                    self.empty = false;
+                    self.done = !self.config.recover || error.is_limit_exceeded();
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        self.start = self.current;
+        self.line_start = self.line;
+
+        if let Err(error) = self.check_limits(None) {
+            self.done = true;
+            return Some(Err(error));
+        }
+
+        if self.empty && self.config.require_value {
+            self.empty = false;
+            self.done = !self.config.recover;
+            return Some(Err(ScannerError::EmptyInput(self.token_position())));
+        }
+
+        self.done = true;
+
+        let eof = self.create_token(TokenType::Eof, BorrowedLiteral::String(Cow::Borrowed("")));
+
+        Some(Ok(self.attach_trivia(eof, leading)))
+    }
+
+    ///Scan the whole source returning every token and every error found
+    ///
+    ///Only stops at the first error unless [`ScannerConfig::recover`] is set.
+    pub fn scan_all(self) -> (Vec<Token>, Vec<ScannerError>) {
+        let mut tokens = vec![];
+        let mut errors = vec![];
+
+        for res in self {
+            match res {
+                Ok(token) => tokens.push(token),
+                Err(error) => errors.push(error),
+            }
         }
 
-        Ok(tokens)
+        (tokens, errors)
     }
 
-    fn eval(&mut self) -> Result<Option<Token>, ScannerError> {
-        let current_char = self.next().unwrap();
+    fn eval(&mut self) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        let current_char = self.advance().unwrap();
 
         let res = match current_char {
-            '\n' => {
-                self.line += 1;
-                self.column_start = 1;
-                self.column_end = 1;
+            ' ' | '\t' | '\r' | '\n' => Ok(None),
+            '\u{feff}' if self.base_offset + self.start == 0 => {
+                self.column_end = self.column_start;
+                self.columns.reset();
                 Ok(None)
             }
-            ' ' | '\t' | '\r' => Ok(None),
-            '{' => Ok(Some(self.create_token(
-                TokenType::LeftBrace,
-                TokenLiteral::String("{".to_string()),
-            ))),
-            '}' => Ok(Some(self.create_token(
-                TokenType::RightBrace,
-                TokenLiteral::String("}".to_string()),
-            ))),
-            '[' => Ok(Some(self.create_token(
-                TokenType::LeftBracket,
-                TokenLiteral::String("[".to_string()),
-            ))),
-            ']' => Ok(Some(self.create_token(
-                TokenType::RightBracket,
-                TokenLiteral::String("]".to_string()),
-            ))),
-            ':' => Ok(Some(self.create_token(
-                TokenType::Colon,
-                TokenLiteral::String(":".to_string()),
-            ))),
-            ',' => Ok(Some(self.create_token(
-                TokenType::Comma,
-                TokenLiteral::String(",".to_string()),
-            ))),
-            '"' => self.eval_string(),
-            '-' => self.eval_numeric(),
-            '+' => self.eval_numeric(),
-            '.' => self.eval_numeric(),
+            '{' | '}' | '[' | ']' | ':' | ',' => Ok(self.structural(current_char)),
+            '"' => self.eval_string('"'),
+            '\'' if self.config.dialect.allows_single_quotes() => self.eval_string('\''),
+            '/' if self.config.dialect.allows_comments() => self.eval_comment(),
+            '-' | '+' | '.' => self.eval_numeric(current_char),
             _ => {
                 if self.is_numeric(current_char) {
-                    self.eval_numeric()
+                    self.eval_numeric(current_char)
+                } else if self.config.dialect.allows_identifiers()
+                    && self.is_identifier_start(current_char)
+                {
+                    self.eval_identifier(current_char)
                 } else if self.is_alpha(current_char) {
                     self.eval_keyword()
+                } else if self.config.dialect.allows_extended_whitespace()
+                    && self.is_extended_whitespace(current_char)
+                {
+                    Ok(None)
                 } else {
                     Err(ScannerError::UnknownCharacter(
-                        TokenPosition::new(self.line, self.column_start, self.column_end),
+                        self.token_position(),
                         current_char,
                     ))
                 }
@@ -176,290 +370,2238 @@ impl Scanner {
         res
     }
 
-    fn eval_numeric(&mut self) -> Result<Option<Token>, ScannerError> {
-        while matches!(self.peek(), Some(char) if self.is_numeric(char)) {
-            self.next();
+    ///Scan the next token straight from the structural index, when it is one the index covers
+    ///
+    ///Skips the general loop of [`Scanner::next_borrowed`], which only trivia and the tokens
+    ///[`Scanner::eval_indexed`] leaves alone need.
+    fn next_indexed(&mut self) -> Option<Result<BorrowedToken<'a>, ScannerError>> {
+        if self.config.trivia {
+            return None;
         }
 
-        if matches!(self.peek(), Some(char) if char == '.') {
-            self.next();
+        self.skip_indexed_whitespace();
 
-            if matches!(self.peek(), Some(char) if self.is_numeric(char)) {
-                while matches!(self.peek(), Some(char) if self.is_numeric(char)) {
-                    self.next();
-                }
+        self.start = self.current;
+        self.line_start = self.line;
+
+        let token = self.eval_indexed()?;
+        self.column_start = self.column_end;
+        self.empty = false;
+
+        match self.check_limits(Some(&token)) {
+            Ok(()) => Some(Ok(token)),
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
             }
         }
+    }
 
-        let number = self
-            .source
-            .get(self.start..self.current)
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
+    ///Take the token at an offset of the structural index in one step, up to the next offset
+    ///
+    ///Covers structural characters, plain strings, which end at the next offset, and numbers and
+    ///literals in the strict JSON grammar, which end at the whitespace or next offset after them.
+    ///Anything else, including every error, is left to the character by character scan.
+    fn eval_indexed(&mut self) -> Option<BorrowedToken<'a>> {
+        let index = self.index.as_ref()?;
+        let hint = index.search(self.current, self.index_hint);
+        let positions = index.positions();
+
+        if positions.get(hint) != Some(&self.current) {
+            return None;
+        }
 
-        Ok(Some(self.create_token(
-            TokenType::Number,
-            TokenLiteral::Number(number),
-        )))
+        self.index_hint = hint;
+
+        let source = self.source;
+        let bytes = source.as_bytes();
+        let next = positions.get(hint + 1).copied().unwrap_or(bytes.len());
+
+        match bytes[self.current] {
+            byte @ (b'{' | b'}' | b'[' | b']' | b':' | b',') => {
+                self.advance_to(self.current + 1);
+                self.structural(char::from(byte))
+            }
+            //A string over the limit is left to `eval_string`, which reports it where it crosses
+            b'"' if index.is_plain_string(hint)
+                && bytes.get(next) == Some(&b'"')
+                && next - self.current - 1 <= self.config.limits.max_string_length =>
+            {
+                self.advance_to(next + 1);
+
+                Some(self.create_token(
+                    TokenType::String,
+                    BorrowedLiteral::String(Cow::Borrowed(&source[self.start + 1..next])),
+                ))
+            }
+            b'-' | b'0'..=b'9' | b't' | b'f' | b'n' => {
+                let end = bytes[self.current..next]
+                    .iter()
+                    .position(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r'))
+                    .map_or(next, |length| self.current + length);
+                let lexeme = &source[self.current..end];
+
+                if matches!(lexeme, "true" | "false" | "null")
+                    && lexeme.len() <= self.config.limits.max_literal_length
+                {
+                    self.advance_to(end);
+                    self.keyword(lexeme)
+                } else if is_json_number(lexeme.as_bytes()) {
+                    self.advance_to(end);
+
+                    Some(self.create_token(TokenType::Number, BorrowedLiteral::Number(lexeme)))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
     }
 
-    fn eval_keyword(&mut self) -> Result<Option<Token>, ScannerError> {
-        while matches!(self.peek(), Some(char) if self.is_alpha(char)) {
-            self.next();
+    ///Scan a number following the RFC 8259 grammar
+    ///
+    ///```text
+    ///number = [ "-" ] int [ frac ] [ exp ]
+    ///int    = "0" / ( digit1-9 *digit )
+    ///frac   = "." 1*digit
+    ///exp    = ( "e" / "E" ) [ "-" / "+" ] 1*digit
+    ///```
+    ///
+    ///Anything number-like is consumed in full before it is validated so that a malformed number
+    ///is reported once, as a whole, with the first rule it breaks.
+    ///
+    ///Dialects with extended numbers also accept a leading `+`, a missing integer or fraction part
+    ///(but not both), hexadecimal integers and a signed `Infinity` or `NaN`.
+    fn eval_numeric(&mut self, first: char) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        let extended = self.config.dialect.allows_extended_numbers();
+        let mut error: Option<fn(TokenPosition, String) -> ScannerError> = None;
+
+        if extended {
+            if matches!(first, '-' | '+')
+                && matches!(self.peek(), Some(char) if self.is_identifier_start(char))
+            {
+                return self.eval_signed_word();
+            }
+
+            if self.is_hex_prefix(first) {
+                return self.eval_hex(first);
+            }
+        } else if first == '+' {
+            error = Some(ScannerError::LeadingPlusSign);
         }
 
-        let word = self.source.get(self.start..self.current).unwrap();
+        let integer_start = if self.is_numeric(first) {
+            self.start
+        } else {
+            self.current
+        };
 
-        match word {
-            "null" => Ok(Some(self.create_token(TokenType::Null, TokenLiteral::Null))),
-            "true" => Ok(Some(
-                self.create_token(TokenType::True, TokenLiteral::Bool(true)),
-            )),
-            "false" => Ok(Some(
-                self.create_token(TokenType::False, TokenLiteral::Bool(false)),
-            )),
-            _ => Err(ScannerError::UnknownLiteral(
-                TokenPosition::new(self.line, self.column_start, self.column_end),
-                word.to_string(),
-            )),
+        if first != '.' {
+            self.eval_digits();
         }
-    }
 
-    fn eval_string(&mut self) -> Result<Option<Token>, ScannerError> {
-        while matches!(self.peek(), Some(char) if char != '"') {
-            self.next();
+        let integer = &self.source[integer_start..self.current];
+        let integer_digits = integer.len();
+        let leading_zero = integer_digits > 1 && integer.starts_with('0');
+        let mut fraction_digits = None;
+
+        if first == '.' || self.peek() == Some('.') {
+            if first != '.' {
+                self.advance();
+            }
+
+            fraction_digits = Some(self.eval_digits());
         }
 
-        if self.peek().is_none() {
-            return Err(ScannerError::UnterminatedString(TokenPosition::new(
-                self.line,
-                self.column_start,
-                self.column_end,
-            )));
+        if integer_digits == 0 && !(extended && fraction_digits > Some(0)) {
+            error.get_or_insert(ScannerError::MissingIntegerDigits);
+        } else if leading_zero {
+            error.get_or_insert(ScannerError::LeadingZero);
+        }
+
+        if fraction_digits == Some(0) && !(extended && integer_digits > 0) {
+            error.get_or_insert(ScannerError::MissingFractionDigits);
+        }
+
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.advance();
+
+            if matches!(self.peek(), Some('-' | '+')) {
+                self.advance();
+            }
+
+            if self.eval_digits() == 0 {
+                error.get_or_insert(ScannerError::MissingExponentDigits);
+            }
         }
 
-        self.next();
+        let lexeme = &self.source[self.start..self.current];
 
-        let sub = self.source.get(self.start + 1..self.current - 1).unwrap();
+        if let Some(error) = error {
+            return Err(error(self.token_position(), lexeme.to_string()));
+        }
 
         Ok(Some(self.create_token(
-            TokenType::String,
-            TokenLiteral::String(sub.to_string()),
+            TokenType::Number,
+            BorrowedLiteral::Number(lexeme),
         )))
     }
 
-    fn create_token(&self, token_type: TokenType, token_literal: TokenLiteral) -> Token {
-        Token::new(
-            token_type,
-            token_literal,
-            TokenPosition::new(self.line, self.column_start, self.column_end),
-        )
-    }
+    ///Consume a run of ascii digits returning how many were found
+    fn eval_digits(&mut self) -> usize {
+        let mut count = 0;
 
-    fn is_alpha(&self, current_char: char) -> bool {
-        (current_char >= 'a' && current_char <= 'z') || (current_char >= 'A' && current_char <= 'Z')
-    }
+        while matches!(self.peek(), Some(char) if self.is_numeric(char)) {
+            self.advance();
+            count += 1;
+        }
 
-    fn is_numeric(&self, current_char: char) -> bool {
-        current_char >= '0' && current_char <= '9'
+        count
     }
 
-    fn peek_next(&mut self) -> Option<char> {
-        self.source.chars().nth(self.current + 1)
-    }
+    ///Scan a hexadecimal integer such as `0x1F` or `-0XFF`, starting after its first character
+    fn eval_hex(&mut self, first: char) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        if first != '0' {
+            self.advance();
+        }
 
-    fn next(&mut self) -> Option<char> {
-        let char = self.source.chars().nth(self.current);
-        self.column_end += 1;
-        self.current += 1;
-        char
-    }
+        self.advance();
 
-    fn peek(&self) -> Option<char> {
-        self.source.chars().nth(self.current)
-    }
-}
+        let mut digits = 0;
 
-#[cfg(test)]
-mod scanner_tests {
-    use crate::{scanner::scanner_error::ScannerError, token::token_position::TokenPosition};
+        while matches!(self.peek(), Some(char) if char.is_ascii_hexdigit()) {
+            self.advance();
+            digits += 1;
+        }
 
-    use super::Scanner;
+        let lexeme = &self.source[self.start..self.current];
 
-    #[test]
-    fn scan_keywords() {
-        let mut s1 = Scanner::new("true");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!("true", r1);
+        if digits == 0 {
+            return Err(ScannerError::MissingIntegerDigits(
+                self.token_position(),
+                lexeme.to_string(),
+            ));
+        }
 
-        let mut s1 = Scanner::new("false");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!("false", r1);
+        Ok(Some(self.create_token(
+            TokenType::Number,
+            BorrowedLiteral::Number(lexeme),
+        )))
+    }
 
-        let mut s1 = Scanner::new("null");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!("null", r1);
+    ///Scan the word after a sign, which has to be `Infinity` or `NaN`
+    fn eval_signed_word(&mut self) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        let lexeme = self.eval_word(Self::is_identifier_part)?;
 
-        let mut s1 = Scanner::new("hello");
-        let r1 = s1.scan();
-        assert_eq!(
-            Err(ScannerError::UnknownLiteral(
-                TokenPosition::new(1, 1, 6),
-                "hello".to_string()
+        match &lexeme[1..] {
+            "Infinity" | "NaN" => Ok(Some(
+                self.create_token(TokenType::Number, BorrowedLiteral::Number(lexeme)),
             )),
-            r1
-        );
+            _ => Err(self.unknown_literal(lexeme)),
+        }
     }
 
-    #[test]
-    fn scan_number() {
-        let mut s1 = Scanner::new(".23");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!(0.23, r1.parse::<f64>().unwrap());
+    fn eval_keyword(&mut self) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        let word = self.eval_word(Self::is_alpha)?;
 
-        let mut s1 = Scanner::new("-2.");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!(-2.0, r1.parse::<f64>().unwrap());
+        match self.keyword(word) {
+            Some(token) => Ok(Some(token)),
+            None => Err(self.unknown_literal(word)),
+        }
+    }
 
-        let mut s1 = Scanner::new("100");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!(100.0, r1.parse::<f64>().unwrap());
+    ///Consume the rest of a bare word made of characters matching `is_part`
+    ///
+    ///Stops as soon as the word is longer than
+    ///[`Limits::max_literal_length`](super::limits::Limits), so that an unknown literal is never
+    ///looked at in full before it is reported.
+    fn eval_word(&mut self, is_part: fn(&Self, char) -> bool) -> Result<&'a str, ScannerError> {
+        let max_length = self.config.limits.max_literal_length;
+
+        loop {
+            if self.current - self.start > max_length {
+                return Err(ScannerError::LiteralTooLong(
+                    self.token_position(),
+                    max_length,
+                ));
+            }
 
-        let mut s1 = Scanner::new("2.52");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!(2.52, r1.parse::<f64>().unwrap());
+            match self.peek() {
+                Some(char) if is_part(self, char) => {
+                    self.advance();
+                }
+                _ => break,
+            }
+        }
 
-        let mut s1 = Scanner::new("-35.5");
-        let r1: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
-        assert_eq!(-35.5, r1.parse::<f64>().unwrap());
+        Ok(&self.source[self.start..self.current])
     }
 
-    #[test]
-    fn peek_next() {
-        let mut s1 = Scanner::new("101");
+    ///Scan an identifier, which may turn out to be a keyword or, with extended numbers, `Infinity`
+    ///or `NaN`
+    ///
+    ///Identifiers follow ECMAScript's IdentifierName and may contain `\uXXXX` escapes, in which
+    ///case the literal holds the unescaped name.
+    fn eval_identifier(&mut self, first: char) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        let source = self.source;
+        let mut name: Option<String> = None;
+
+        if first == '\\' {
+            let escape_start = self.token_start();
+            name = Some(self.eval_identifier_escape(escape_start)?.to_string());
+        }
 
-        assert_eq!(Some('0'), s1.peek_next());
-        s1.next();
-        assert_eq!(Some('1'), s1.peek_next());
-        s1.next();
-        assert_eq!(None, s1.peek_next());
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    let escaped = &source[self.start..self.current];
+                    let name = name.get_or_insert_with(|| escaped.to_string());
+                    let escape_start = self.cursor();
 
-        assert_eq!(2, s1.current);
-    }
+                    self.advance();
+                    name.push(self.eval_identifier_escape(escape_start)?);
+                }
+                Some(char) if self.is_identifier_part(char) => {
+                    self.advance();
 
-    #[test]
-    fn is_char_alpha() {
-        let s1 = Scanner::new("{}");
-        let a1 = s1.is_alpha('9');
-        let a2 = s1.is_alpha('a');
-        let a3 = s1.is_alpha('R');
+                    if let Some(name) = &mut name {
+                        name.push(char);
+                    }
+                }
+                _ => break,
+            }
+        }
 
-        assert_eq!(false, a1);
-        assert_eq!(true, a2);
-        assert_eq!(true, a3);
-    }
+        if let Some(name) = name {
+            return Ok(Some(self.create_token(
+                TokenType::Identifier,
+                BorrowedLiteral::String(Cow::Owned(name)),
+            )));
+        }
 
-    #[test]
-    fn is_char_numeric() {
-        let s1 = Scanner::new("{}");
-        let n1 = s1.is_numeric('9');
-        let n2 = s1.is_numeric('A');
+        let word = &source[self.start..self.current];
 
-        assert_eq!(true, n1);
-        assert_eq!(false, n2);
+        match self.keyword(word) {
+            Some(token) => Ok(Some(token)),
+            None => Ok(Some(self.create_token(
+                TokenType::Identifier,
+                BorrowedLiteral::String(Cow::Borrowed(word)),
+            ))),
+        }
     }
 
-    #[test]
-    fn scan_string() {
-        let mut s1 = Scanner::new("\"Hello, World\"");
+    ///Decode the `uXXXX` following a backslash in an identifier
+    fn eval_identifier_escape(&mut self, escape_start: Cursor) -> Result<char, ScannerError> {
+        match self.advance() {
+            Some('u') => {}
+            Some(char) => {
+                return Err(ScannerError::InvalidEscape(
+                    self.position_from(escape_start),
+                    char,
+                ))
+            }
+            None => {
+                return Err(ScannerError::UnknownCharacter(
+                    self.position_from(escape_start),
+                    '\\',
+                ))
+            }
+        }
 
-        let scan: String = s1
-            .scan()
-            .unwrap()
-            .get(0)
-            .unwrap()
-            .token_literal
-            .clone()
-            .into();
+        let code_unit = self.eval_unicode_escape(escape_start)?;
 
-        assert_eq!("Hello, World", scan);
+        char::from_u32(u32::from(code_unit)).ok_or(ScannerError::UnpairedSurrogate(
+            self.position_from(escape_start),
+            code_unit,
+        ))
     }
 
-    #[test]
-    fn update_column_start_and_end() {
-        let mut s1 = Scanner::new("{\n}");
-        let res = s1.scan().unwrap();
+    ///The token for one of the structural characters `{}[]:,`
+    fn structural(&self, char: char) -> Option<BorrowedToken<'a>> {
+        let (token_type, lexeme) = match char {
+            '{' => (TokenType::LeftBrace, "{"),
+            '}' => (TokenType::RightBrace, "}"),
+            '[' => (TokenType::LeftBracket, "["),
+            ']' => (TokenType::RightBracket, "]"),
+            ':' => (TokenType::Colon, ":"),
+            ',' => (TokenType::Comma, ","),
+            _ => return None,
+        };
 
-        assert_eq!(
-            TokenPosition::new(1, 1, 2),
-            res.get(0).unwrap().token_position
-        );
-        assert_eq!(
-            TokenPosition::new(2, 1, 2),
-            res.get(1).unwrap().token_position
-        );
+        Some(self.create_token(token_type, BorrowedLiteral::String(Cow::Borrowed(lexeme))))
     }
 
-    #[test]
-    fn scan_error() {
-        let mut s1 = Scanner::new("@");
+    fn keyword(&self, word: &'a str) -> Option<BorrowedToken<'a>> {
+        let (token_type, token_literal) = match word {
+            "null" => (TokenType::Null, BorrowedLiteral::Null),
+            "true" => (TokenType::True, BorrowedLiteral::Bool(true)),
+            "false" => (TokenType::False, BorrowedLiteral::Bool(false)),
+            "Infinity" | "NaN" if self.config.dialect.allows_extended_numbers() => {
+                (TokenType::Number, BorrowedLiteral::Number(word))
+            }
+            _ => return None,
+        };
+
+        Some(self.create_token(token_type, token_literal))
+    }
+
+    ///Report a word that is not a literal, suggesting the literal that was most likely meant
+    fn unknown_literal(&self, word: &str) -> ScannerError {
+        let position = self.token_position();
+        let suggestion = self.suggest_literal(word).map(|(literal, applicability)| {
+            Box::new(Suggestion::new(position, literal, applicability))
+        });
+
+        ScannerError::UnknownLiteral(position, word.to_string(), suggestion)
+    }
+
+    ///Find the literal closest to `word`
+    ///
+    ///A literal that only differs in case or is a well known foreign spelling, such as `None` or
+    ///`nil`, is a safe fix. Otherwise the literal with the smallest edit distance is suggested
+    ///when it is close enough to be a typo.
+    fn suggest_literal(&self, word: &str) -> Option<(String, Applicability)> {
+        let (sign, unsigned) = match word.strip_prefix(['+', '-']) {
+            Some(unsigned) => (&word[..1], unsigned),
+            None => ("", word),
+        };
+
+        let mut literals = vec![];
+        if sign.is_empty() {
+            literals.extend(["null", "true", "false"]);
+        }
+        if self.config.dialect.allows_extended_numbers() {
+            literals.extend(["Infinity", "NaN"]);
+        }
+
+        let lowercase = unsigned.to_lowercase();
+        let known = literals
+            .iter()
+            .copied()
+            .find(|literal| literal.to_lowercase() == lowercase)
+            .or_else(|| {
+                FOREIGN_LITERALS
+                    .iter()
+                    .find(|(spelling, _)| *spelling == lowercase)
+                    .map(|(_, literal)| *literal)
+                    .filter(|literal| literals.contains(literal))
+            });
+
+        if let Some(literal) = known {
+            return Some((
+                format!("{}{}", sign, literal),
+                Applicability::MachineApplicable,
+            ));
+        }
+
+        let (literal, distance) = literals
+            .iter()
+            .filter_map(|literal| {
+                edit_distance(&lowercase, &literal.to_lowercase(), 2)
+                    .map(|distance| (*literal, distance))
+            })
+            .min_by_key(|(_, distance)| *distance)?;
+
+        let applicability = match distance {
+            1 => Applicability::MachineApplicable,
+            2 if lowercase.chars().count() > 3 => Applicability::MaybeIncorrect,
+            _ => return None,
+        };
+
+        Some((format!("{}{}", sign, literal), applicability))
+    }
+
+    ///Scan a `//` comment up to the end of its line or a `/* */` comment up to its closing `*/`
+    ///
+    ///The literal is the whole comment including its delimiters, but not the line ending after a
+    ///line comment.
+    fn eval_comment(&mut self) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        match self.peek() {
+            Some('/') => {
+                while matches!(self.peek(), Some(char) if !self.is_line_terminator(char)) {
+                    self.advance();
+                }
+            }
+            Some('*') => {
+                self.advance();
+
+                loop {
+                    match self.advance() {
+                        Some('*') if self.peek() == Some('/') => {
+                            self.advance();
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            return Err(ScannerError::UnterminatedBlockComment(
+                                self.token_position(),
+                            ))
+                        }
+                    }
+                }
+            }
+            _ => return Err(ScannerError::UnknownCharacter(self.token_position(), '/')),
+        }
+
+        let text = &self.source[self.start..self.current];
+
+        Ok(Some(self.create_token(
+            TokenType::Comment,
+            BorrowedLiteral::String(Cow::Borrowed(text)),
+        )))
+    }
+
+    ///Scan a string literal delimited by `quote`
+    ///
+    ///The content is only copied once an escape sequence is found, until then the literal is a
+    ///slice of the source.
+    ///
+    ///Control characters must be escaped. When recovering, a raw line ending is taken to be the end
+    ///of a string that is missing its closing quote, so scanning resumes on the next line.
+    fn eval_string(&mut self, quote: char) -> Result<Option<BorrowedToken<'a>>, ScannerError> {
+        let source = self.source;
+        let max_length = self.config.limits.max_string_length;
+        let mut value: Option<String> = None;
+        let mut error: Option<ScannerError> = None;
+
+        loop {
+            let length = value
+                .as_ref()
+                .map_or(self.current - self.start - 1, String::len);
+
+            if length > max_length {
+                return Err(ScannerError::StringTooLong(
+                    self.token_position(),
+                    max_length,
+                ));
+            }
+
+            match self.peek() {
+                None => {
+                    return Err(
+                        error.unwrap_or(ScannerError::UnterminatedString(self.token_position()))
+                    )
+                }
+                Some(char) if char == quote => break,
+                Some('\\')
+                    if self.config.dialect.allows_extended_escapes()
+                        && matches!(self.peek_next(), Some(char) if self.is_line_terminator(char)) =>
+                {
+                    let unescaped = &source[self.start + 1..self.current];
+                    value.get_or_insert_with(|| unescaped.to_string());
+
+                    self.eval_line_continuation();
+                }
+                Some('\\') => {
+                    let unescaped = &source[self.start + 1..self.current];
+                    let value = value.get_or_insert_with(|| unescaped.to_string());
+
+                    match self.eval_escape() {
+                        Ok(char) => value.push(char),
+                        Err(escape_error) if self.config.recover => {
+                            error.get_or_insert(escape_error);
+                        }
+                        Err(escape_error) => return Err(escape_error),
+                    }
+                }
+                Some(char) => {
+                    let cursor = self.cursor();
+
+                    self.advance();
+
+                    if self.is_control_character(char) {
+                        let control =
+                            ScannerError::ControlCharacter(self.char_position(cursor, char), char);
+
+                        if !self.config.recover {
+                            return Err(control);
+                        }
+
+                        if matches!(char, '\n' | '\r') {
+                            self.rewind(cursor);
+                            return Err(error.unwrap_or(control));
+                        }
+
+                        error.get_or_insert(control);
+                    }
+
+                    if let Some(value) = &mut value {
+                        value.push(char);
+                    }
+                }
+            }
+        }
+
+        self.advance();
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let value = match value {
+            Some(value) => Cow::Owned(value),
+            None => Cow::Borrowed(&source[self.start + 1..self.current - 1]),
+        };
+
+        Ok(Some(self.create_token(
+            TokenType::String,
+            BorrowedLiteral::String(value),
+        )))
+    }
+
+    ///Skip a backslash followed by a line terminator, which leaves nothing in the string
+    fn eval_line_continuation(&mut self) {
+        self.advance();
+
+        if self.advance() == Some('\r') && self.peek() == Some('\n') {
+            self.advance();
+        }
+    }
+
+    ///Decode a single escape sequence starting at the backslash under the cursor
+    ///
+    ///`\uXXXX` escapes in the UTF-16 surrogate range must come in high/low pairs, which are
+    ///combined into one character e.g. `\uD83D\uDE00` becomes `😀`.
+    ///
+    ///With extended escapes `\'`, `\v`, `\0` and `\xXX` are recognised too, and any other
+    ///character except a digit stands for itself.
+    fn eval_escape(&mut self) -> Result<char, ScannerError> {
+        let extended = self.config.dialect.allows_extended_escapes();
+        let escape_start = self.cursor();
+
+        self.advance();
+
+        let Some(escaped) = self.advance() else {
+            return Err(ScannerError::UnterminatedString(self.token_position()));
+        };
+
+        match escaped {
+            '"' => Ok('"'),
+            '\\' => Ok('\\'),
+            '/' => Ok('/'),
+            'b' => Ok('\u{8}'),
+            'f' => Ok('\u{c}'),
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            'u' => {
+                let high = self.eval_unicode_escape(escape_start)?;
+
+                match high {
+                    0xD800..=0xDBFF => {
+                        if self.peek() != Some('\\') || self.peek_next() != Some('u') {
+                            return Err(ScannerError::UnpairedSurrogate(
+                                self.position_from(escape_start),
+                                high,
+                            ));
+                        }
+
+                        let low_start = self.cursor();
+
+                        self.advance();
+                        self.advance();
+
+                        let low = self.eval_unicode_escape(low_start)?;
+
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return Err(ScannerError::UnpairedSurrogate(
+                                self.position_between(escape_start, low_start),
+                                high,
+                            ));
+                        }
+
+                        let code_point = 0x10000
+                            + ((u32::from(high) - 0xD800) << 10)
+                            + (u32::from(low) - 0xDC00);
+
+                        Ok(char::from_u32(code_point).unwrap())
+                    }
+                    0xDC00..=0xDFFF => Err(ScannerError::UnpairedSurrogate(
+                        self.position_from(escape_start),
+                        high,
+                    )),
+                    _ => Ok(char::from_u32(u32::from(high)).unwrap()),
+                }
+            }
+            '\'' if extended => Ok('\''),
+            'v' if extended => Ok('\u{b}'),
+            '0' if extended && !matches!(self.peek(), Some(char) if self.is_numeric(char)) => {
+                Ok('\0')
+            }
+            'x' if extended => self.eval_hex_escape(escape_start),
+            _ if extended && !self.is_numeric(escaped) => Ok(escaped),
+            _ => Err(ScannerError::InvalidEscape(
+                self.position_from(escape_start),
+                escaped,
+            )),
+        }
+    }
+
+    ///Read the two hex digits of a `\xXX` escape whose backslash is at `escape_start`
+    fn eval_hex_escape(&mut self, escape_start: Cursor) -> Result<char, ScannerError> {
+        let mut code_point = 0;
+
+        for _ in 0..2 {
+            match self.peek().and_then(|char| char.to_digit(16)) {
+                Some(digit) => {
+                    self.advance();
+                    code_point = code_point * 16 + digit;
+                }
+                None => {
+                    return Err(ScannerError::InvalidEscape(
+                        self.position_from(escape_start),
+                        'x',
+                    ))
+                }
+            }
+        }
+
+        Ok(char::from_u32(code_point).unwrap())
+    }
+
+    ///Read the four hex digits of a `\uXXXX` escape whose backslash is at `escape_start`
+    fn eval_unicode_escape(&mut self, escape_start: Cursor) -> Result<u16, ScannerError> {
+        let mut digits = String::new();
+
+        while digits.len() < 4 {
+            match self.peek() {
+                Some(char) if char.is_ascii_hexdigit() => {
+                    self.advance();
+                    digits.push(char);
+                }
+                _ => {
+                    return Err(ScannerError::InvalidUnicodeEscape(
+                        self.position_from(escape_start),
+                        digits,
+                    ))
+                }
+            }
+        }
+
+        Ok(u16::from_str_radix(&digits, 16).unwrap())
+    }
+
+    ///Record the text from `start` to the cursor as trivia, merging runs of whitespace and the two
+    ///halves of a `\r\n`
+    fn push_trivia(&self, trivia: &mut Vec<Trivia<'a>>, kind: TriviaKind) {
+        let position = self.token_position();
+
+        if let Some(last) = trivia.last_mut() {
+            let merge = match (last.kind, kind) {
+                (TriviaKind::Whitespace, TriviaKind::Whitespace) => true,
+                (TriviaKind::Newline, TriviaKind::Newline) => {
+                    last.text == "\r" && &self.source[self.start..self.current] == "\n"
+                }
+                _ => false,
+            };
+
+            if merge {
+                let start = last.position.offset_start - self.base_offset;
+
+                last.text = Cow::Borrowed(&self.source[start..self.current]);
+                last.position = last.position.to(&position);
+                return;
+            }
+        }
+
+        trivia.push(Trivia::new(
+            kind,
+            &self.source[self.start..self.current],
+            position,
+        ));
+    }
+
+    ///Attach the leading trivia already scanned to `token` and scan its trailing trivia
+    ///
+    ///Trailing trivia ends after the first line ending. Does nothing unless
+    ///[`ScannerConfig::trivia`] is set.
+    fn attach_trivia(
+        &mut self,
+        mut token: BorrowedToken<'a>,
+        leading: Vec<Trivia<'a>>,
+    ) -> BorrowedToken<'a> {
+        if !self.config.trivia {
+            return token;
+        }
+
+        let lexeme = &self.source[self.start..self.current];
+        let mut trailing = vec![];
+
+        loop {
+            self.start = self.current;
+            self.line_start = self.line;
+            self.column_start = self.column_end;
+
+            let kind = match self.peek() {
+                Some('\n') => {
+                    self.advance();
+                    TriviaKind::Newline
+                }
+                Some('\r') => {
+                    self.advance();
+
+                    if self.peek() == Some('\n') {
+                        self.advance();
+                    }
+
+                    TriviaKind::Newline
+                }
+                Some(' ' | '\t') => {
+                    self.advance();
+                    TriviaKind::Whitespace
+                }
+                Some(char)
+                    if self.config.dialect.allows_extended_whitespace()
+                        && self.is_extended_whitespace(char) =>
+                {
+                    self.advance();
+                    TriviaKind::Whitespace
+                }
+                Some('/')
+                    if self.config.dialect.allows_comments()
+                        && matches!(self.peek_next(), Some('/' | '*')) =>
+                {
+                    let comment_start = self.cursor();
+
+                    self.advance();
+
+                    if self.eval_comment().is_err() {
+                        self.rewind(comment_start);
+                        break;
+                    }
+
+                    TriviaKind::Comment
+                }
+                _ => break,
+            };
+
+            self.push_trivia(&mut trailing, kind);
+
+            if kind == TriviaKind::Newline {
+                break;
+            }
+        }
+
+        self.start = self.current;
+        self.line_start = self.line;
+        self.column_start = self.column_end;
+
+        token.trivia = Some(Box::new(TokenTrivia {
+            leading,
+            lexeme: Cow::Borrowed(lexeme),
+            trailing,
+        }));
+
+        token
+    }
+
+    ///Check the token just scanned against [`ScannerConfig::limits`]
+    ///
+    ///Without a token, after whitespace or at the end of input, only the document size is checked
+    ///and reported at the cursor.
+    fn check_limits(&mut self, token: Option<&BorrowedToken>) -> Result<(), ScannerError> {
+        let limits = self.config.limits;
+
+        let Some(token) = token else {
+            return match self.base_offset + self.current > limits.max_document_size {
+                true => Err(ScannerError::DocumentTooLarge(
+                    self.position_from(self.cursor()),
+                    limits.max_document_size,
+                )),
+                false => Ok(()),
+            };
+        };
+
+        if self.base_offset + self.current > limits.max_document_size {
+            return Err(ScannerError::DocumentTooLarge(
+                token.token_position,
+                limits.max_document_size,
+            ));
+        }
+
+        match (token.token_type, &token.token_literal) {
+            (TokenType::String | TokenType::Identifier, BorrowedLiteral::String(value))
+                if value.len() > limits.max_string_length =>
+            {
+                return Err(ScannerError::StringTooLong(
+                    token.token_position,
+                    limits.max_string_length,
+                ));
+            }
+            (TokenType::Number, BorrowedLiteral::Number(lexeme))
+                if lexeme.len() > limits.max_number_length =>
+            {
+                return Err(ScannerError::NumberTooLong(
+                    token.token_position,
+                    limits.max_number_length,
+                ));
+            }
+            _ => {}
+        }
+
+        self.token_count += 1;
+
+        match self.token_count > limits.max_tokens {
+            true => Err(ScannerError::TooManyTokens(
+                token.token_position,
+                limits.max_tokens,
+            )),
+            false => Ok(()),
+        }
+    }
+
+    fn create_token(
+        &self,
+        token_type: TokenType,
+        token_literal: BorrowedLiteral<'a>,
+    ) -> BorrowedToken<'a> {
+        BorrowedToken::new(token_type, token_literal, self.token_position())
+    }
+
+    ///Position of the token being scanned, from its first character up to the cursor
+    pub(crate) fn token_position(&self) -> TokenPosition {
+        self.position_from(self.token_start())
+    }
+
+    fn token_start(&self) -> Cursor {
+        Cursor {
+            offset: self.start,
+            line: self.line_start,
+            column: self.column_start,
+        }
+    }
+
+    ///Position of the single character at `start`, kept on its line even when it is a line ending
+    fn char_position(&self, start: Cursor, char: char) -> TokenPosition {
+        let offset = self.base_offset + start.offset;
+
+        TokenPosition::new(
+            start.line,
+            start.line,
+            start.column,
+            start.column + 1,
+            offset,
+            offset + char.len_utf8(),
+        )
+    }
+
+    pub(crate) fn position_from(&self, start: Cursor) -> TokenPosition {
+        self.position_between(start, self.cursor())
+    }
+
+    fn position_between(&self, start: Cursor, end: Cursor) -> TokenPosition {
+        TokenPosition::new(
+            start.line,
+            end.line,
+            start.column,
+            end.column,
+            self.base_offset + start.offset,
+            self.base_offset + end.offset,
+        )
+    }
+
+    pub(crate) fn cursor(&self) -> Cursor {
+        Cursor {
+            offset: self.current,
+            line: self.line,
+            column: self.column_end,
+        }
+    }
+
+    fn rewind(&mut self, cursor: Cursor) {
+        self.current = cursor.offset;
+        self.line = cursor.line;
+        self.column_end = cursor.column;
+    }
+
+    fn is_alpha(&self, current_char: char) -> bool {
+        current_char.is_ascii_alphabetic()
+    }
+
+    fn is_numeric(&self, current_char: char) -> bool {
+        current_char.is_ascii_digit()
+    }
+
+    fn is_identifier_start(&self, current_char: char) -> bool {
+        current_char.is_alphabetic() || matches!(current_char, '$' | '_' | '\\')
+    }
+
+    fn is_identifier_part(&self, current_char: char) -> bool {
+        current_char.is_alphanumeric()
+            || matches!(current_char, '$' | '_' | '\u{200c}' | '\u{200d}')
+            || unicode::is_extend(current_char)
+    }
+
+    fn is_hex_prefix(&mut self, first: char) -> bool {
+        match first {
+            '0' => matches!(self.peek(), Some('x' | 'X')),
+            '-' | '+' => self.peek() == Some('0') && matches!(self.peek_next(), Some('x' | 'X')),
+            _ => false,
+        }
+    }
+
+    ///Whether `current_char` has to be escaped inside a string
+    fn is_control_character(&self, current_char: char) -> bool {
+        if self.config.dialect.allows_raw_control_characters() {
+            matches!(current_char, '\n' | '\r')
+        } else {
+            current_char < ' '
+        }
+    }
+
+    fn is_line_terminator(&self, current_char: char) -> bool {
+        matches!(current_char, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+    }
+
+    ///Whitespace beyond JSON's space, tab and line endings: vertical tab, form feed, the byte
+    ///order mark and the Unicode space separators
+    fn is_extended_whitespace(&self, current_char: char) -> bool {
+        matches!(
+            current_char,
+            '\u{b}' | '\u{c}' | '\u{a0}' | '\u{1680}' | '\u{2000}'
+                ..='\u{200a}'
+                    | '\u{2028}'
+                    | '\u{2029}'
+                    | '\u{202f}'
+                    | '\u{205f}'
+                    | '\u{3000}'
+                    | '\u{feff}'
+        )
+    }
+
+    fn peek_next(&mut self) -> Option<char> {
+        let mut chars = self.source.get(self.current..)?.chars();
+        chars.next();
+
+        let char = chars.next();
+        self.exhausted |= char.is_none();
+        char
+    }
+
+    ///Move the cursor past the character under it, keeping track of lines and columns
+    ///
+    ///Columns are counted in [`ScannerConfig::column_unit`].
+    fn advance(&mut self) -> Option<char> {
+        let char = self.peek()?;
+
+        self.current += char.len_utf8();
+
+        if char == '\n' {
+            self.line += 1;
+            self.column_end = 1;
+            self.columns.reset();
+        } else {
+            self.column_end = self
+                .columns
+                .advance(self.config.column_unit, self.column_end, char);
+        }
+
+        Some(char)
+    }
+
+    ///Jump over the whitespace before the next offset in the structural index
+    ///
+    ///Stops at [`Limits::max_document_size`](super::limits::Limits) so that the whitespace
+    ///crossing it is scanned and reported as usual.
+    fn skip_indexed_whitespace(&mut self) {
+        let Some(index) = &self.index else {
+            return;
+        };
+
+        let hint = index.search(self.current, self.index_hint);
+        let limit = self
+            .config
+            .limits
+            .max_document_size
+            .saturating_sub(self.base_offset)
+            .max(self.current);
+        let end = index
+            .positions()
+            .get(hint)
+            .copied()
+            .unwrap_or(self.source.len())
+            .min(limit);
+
+        self.index_hint = hint;
+
+        let gap = &self.source.as_bytes()[self.current..end];
+
+        if gap.is_empty()
+            || !gap
+                .iter()
+                .all(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r'))
+        {
+            return;
+        }
+
+        if let Some(last) = gap.iter().rposition(|byte| *byte == b'\n') {
+            self.line += gap.iter().filter(|byte| **byte == b'\n').count();
+            self.current += last + 1;
+            self.column_end = 1;
+            self.columns.reset();
+        }
+
+        self.advance_to(end);
+        self.column_start = self.column_end;
+    }
+
+    ///Move the cursor to `end` over text without line feeds, only decoding characters when the
+    ///column unit needs them
+    fn advance_to(&mut self, end: usize) {
+        let text = &self.source[self.current..end];
+        let unit = self.config.column_unit;
+
+        self.column_end = match unit {
+            ColumnUnit::Utf8 => self.column_end + text.len(),
+            ColumnUnit::Scalar => {
+                self.column_end + text.bytes().filter(|byte| byte & 0xc0 != 0x80).count()
+            }
+            ColumnUnit::Utf16 => {
+                self.column_end
+                    + text
+                        .bytes()
+                        .map(|byte| match byte {
+                            0x80..=0xbf => 0,
+                            0xf0.. => 2,
+                            _ => 1,
+                        })
+                        .sum::<usize>()
+            }
+            _ => text.chars().fold(self.column_end, |column, char| {
+                self.columns.advance(unit, column, char)
+            }),
+        };
+
+        self.current = end;
+    }
+
+    ///Look at the character under the cursor
+    ///
+    ///Looking past the end of the source is recorded in `exhausted`, which tells the
+    ///[`StreamScanner`](super::stream_scanner::StreamScanner) that a token may continue in input it
+    ///has not read yet.
+    fn peek(&mut self) -> Option<char> {
+        let char = match self.source.as_bytes().get(self.current) {
+            Some(byte) if byte.is_ascii() => Some(char::from(*byte)),
+            Some(_) => self.source[self.current..].chars().next(),
+            None => None,
+        };
+
+        self.exhausted |= char.is_none();
+        char
+    }
+}
+
+///Whether `lexeme` is a number in the strict RFC 8259 grammar, which every dialect accepts
+fn is_json_number(lexeme: &[u8]) -> bool {
+    let digits = |from: usize| {
+        lexeme[from.min(lexeme.len())..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+
+    let mut end = usize::from(lexeme.first() == Some(&b'-'));
+
+    match lexeme.get(end) {
+        Some(b'0') => end += 1,
+        Some(b'1'..=b'9') => end += digits(end),
+        _ => return false,
+    }
+
+    if lexeme.get(end) == Some(&b'.') {
+        match digits(end + 1) {
+            0 => return false,
+            count => end += 1 + count,
+        }
+    }
+
+    if matches!(lexeme.get(end), Some(b'e' | b'E')) {
+        end += 1;
+
+        if matches!(lexeme.get(end), Some(b'-' | b'+')) {
+            end += 1;
+        }
+
+        match digits(end) {
+            0 => return false,
+            count => end += count,
+        }
+    }
+
+    end == lexeme.len()
+}
+
+///A point in the source that positions can be reported from or the scanner can rewind to
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Cursor {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+///Lazily scan one token at a time
+///
+///Whitespace is skipped between tokens and nothing is scanned ahead of what the caller asks for,
+///so a consumer can stop early without paying for the rest of the input. After an error the
+///iterator is finished and only returns `None`, unless [`ScannerConfig::recover`] is set in which
+///case it carries on with the next token.
+impl Iterator for Scanner<'_> {
+    type Item = Result<Token, ScannerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_borrowed()
+            .map(|res| res.map(BorrowedToken::into_owned))
+    }
+}
+
+#[cfg(test)]
+mod scanner_tests {
+    use std::borrow::Cow;
+
+    use crate::{
+        diagnostic::suggestion::{Applicability, Suggestion},
+        scanner::{
+            dialect::Dialect, limits::Limits, scanner_config::ScannerConfig,
+            scanner_error::ScannerError,
+        },
+        token::{
+            borrowed_literal::BorrowedLiteral,
+            borrowed_token::BorrowedToken,
+            number::Number,
+            token::Token,
+            token_literal::TokenLiteral,
+            token_position::TokenPosition,
+            token_type::TokenType,
+            trivia::{Trivia, TriviaKind},
+        },
+    };
+
+    use super::Scanner;
+
+    #[test]
+    fn scan_keywords() {
+        let s1 = Scanner::new("true");
+        let r1: String = s1
+            .scan()
+            .unwrap()
+            .first()
+            .unwrap()
+            .token_literal
+            .clone()
+            .into();
+        assert_eq!("true", r1);
+
+        let s1 = Scanner::new("false");
+        let r1: String = s1
+            .scan()
+            .unwrap()
+            .first()
+            .unwrap()
+            .token_literal
+            .clone()
+            .into();
+        assert_eq!("false", r1);
+
+        let s1 = Scanner::new("null");
+        let r1: String = s1
+            .scan()
+            .unwrap()
+            .first()
+            .unwrap()
+            .token_literal
+            .clone()
+            .into();
+        assert_eq!("null", r1);
+
+        let s1 = Scanner::new("hello");
+        let r1 = s1.scan();
+        assert_eq!(
+            Err(ScannerError::UnknownLiteral(
+                TokenPosition::new(1, 1, 1, 6, 0, 5),
+                "hello".to_string(),
+                None
+            )),
+            r1
+        );
+    }
+
+    #[test]
+    fn scan_number() {
+        let numbers = [
+            ("0", 0.0),
+            ("-0", 0.0),
+            ("0.23", 0.23),
+            ("-2.0", -2.0),
+            ("100", 100.0),
+            ("2.52", 2.52),
+            ("-35.5", -35.5),
+            ("1e10", 1e10),
+            ("1E+2", 100.0),
+            ("-2.5E-3", -2.5e-3),
+            ("0e0", 0.0),
+        ];
+
+        for (source, expected) in numbers {
+            let s1 = Scanner::new(source);
+            let r1: String = s1.scan().unwrap()[0].token_literal.clone().into();
+
+            assert_eq!(expected, r1.parse::<f64>().unwrap(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn scan_number_lossless() {
+        let s1 = Scanner::new("[9007199254740993, 1.10, -0.0e+00]");
+        let res = s1.scan().unwrap();
+
+        assert_eq!(
+            TokenLiteral::Number(Number::new("9007199254740993")),
+            res[1].token_literal
+        );
+        assert_eq!(
+            TokenLiteral::Number(Number::new("1.10")),
+            res[3].token_literal
+        );
+        assert_eq!(
+            TokenLiteral::Number(Number::new("-0.0e+00")),
+            res[5].token_literal
+        );
+    }
+
+    #[test]
+    fn scan_malformed_number() {
+        let s1 = Scanner::new("+1");
+        assert_eq!(
+            Err(ScannerError::LeadingPlusSign(
+                TokenPosition::new(1, 1, 1, 3, 0, 2),
+                "+1".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new(".5");
+        assert_eq!(
+            Err(ScannerError::MissingIntegerDigits(
+                TokenPosition::new(1, 1, 1, 3, 0, 2),
+                ".5".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("[-]");
+        assert_eq!(
+            Err(ScannerError::MissingIntegerDigits(
+                TokenPosition::new(1, 1, 2, 3, 1, 2),
+                "-".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("012");
+        assert_eq!(
+            Err(ScannerError::LeadingZero(
+                TokenPosition::new(1, 1, 1, 4, 0, 3),
+                "012".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("-00.5");
+        assert_eq!(
+            Err(ScannerError::LeadingZero(
+                TokenPosition::new(1, 1, 1, 6, 0, 5),
+                "-00.5".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("1.");
+        assert_eq!(
+            Err(ScannerError::MissingFractionDigits(
+                TokenPosition::new(1, 1, 1, 3, 0, 2),
+                "1.".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("1.e5");
+        assert_eq!(
+            Err(ScannerError::MissingFractionDigits(
+                TokenPosition::new(1, 1, 1, 5, 0, 4),
+                "1.e5".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("1e");
+        assert_eq!(
+            Err(ScannerError::MissingExponentDigits(
+                TokenPosition::new(1, 1, 1, 3, 0, 2),
+                "1e".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new("-2.5E-");
+        assert_eq!(
+            Err(ScannerError::MissingExponentDigits(
+                TokenPosition::new(1, 1, 1, 7, 0, 6),
+                "-2.5E-".to_string()
+            )),
+            s1.scan()
+        );
+    }
+
+    #[test]
+    fn peek_next() {
+        let mut s1 = Scanner::new("101");
+
+        assert_eq!(Some('0'), s1.peek_next());
+        s1.advance();
+        assert_eq!(Some('1'), s1.peek_next());
+        s1.advance();
+        assert_eq!(None, s1.peek_next());
+
+        assert_eq!(2, s1.current);
+    }
+
+    #[test]
+    fn advance_multi_byte_characters() {
+        let mut s1 = Scanner::new("é😀a");
+
+        assert_eq!(Some('😀'), s1.peek_next());
+        assert_eq!(Some('é'), s1.advance());
+        assert_eq!(2, s1.current);
+        assert_eq!(Some('😀'), s1.advance());
+        assert_eq!(6, s1.current);
+        assert_eq!(Some('a'), s1.peek());
+        assert_eq!(3, s1.column_end);
+    }
+
+    #[test]
+    fn scan_multi_byte_string() {
+        let s1 = Scanner::new("[\"héllo 😀\", true]");
+        let res = s1.scan().unwrap();
+
+        assert_eq!(
+            TokenLiteral::String("héllo 😀".to_string()),
+            res[1].token_literal
+        );
+        assert_eq!(
+            TokenPosition::new(1, 1, 2, 11, 1, 14),
+            res[1].token_position
+        );
+        assert_eq!(
+            TokenPosition::new(1, 1, 13, 17, 16, 20),
+            res[3].token_position
+        );
+    }
+
+    #[test]
+    fn is_char_alpha() {
+        let s1 = Scanner::new("{}");
+        let a1 = s1.is_alpha('9');
+        let a2 = s1.is_alpha('a');
+        let a3 = s1.is_alpha('R');
+
+        assert!(!a1);
+        assert!(a2);
+        assert!(a3);
+    }
+
+    #[test]
+    fn is_char_numeric() {
+        let s1 = Scanner::new("{}");
+        let n1 = s1.is_numeric('9');
+        let n2 = s1.is_numeric('A');
+
+        assert!(n1);
+        assert!(!n2);
+    }
+
+    #[test]
+    fn scan_string() {
+        let s1 = Scanner::new("\"Hello, World\"");
+
+        let scan: String = s1
+            .scan()
+            .unwrap()
+            .first()
+            .unwrap()
+            .token_literal
+            .clone()
+            .into();
+
+        assert_eq!("Hello, World", scan);
+    }
+
+    #[test]
+    fn scan_string_escapes() {
+        let s1 = Scanner::new(r#""a\"b\\c\/d\b\f\n\r\t""#);
+        let r1: String = s1.scan().unwrap()[0].token_literal.clone().into();
+        assert_eq!("a\"b\\c/d\u{8}\u{c}\n\r\t", r1);
+
+        let s1 = Scanner::new(r#""caf\u00e9 \u2603""#);
+        let r1: String = s1.scan().unwrap()[0].token_literal.clone().into();
+        assert_eq!("café ☃", r1);
+
+        let s1 = Scanner::new(r#""\uD83D\uDE00""#);
+        let r1: String = s1.scan().unwrap()[0].token_literal.clone().into();
+        assert_eq!("😀", r1);
+    }
+
+    #[test]
+    fn scan_string_escape_errors() {
+        let s1 = Scanner::new(r#""ab\q""#);
+        assert_eq!(
+            Err(ScannerError::InvalidEscape(
+                TokenPosition::new(1, 1, 4, 6, 3, 5),
+                'q'
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new(r#""\u12G4""#);
+        assert_eq!(
+            Err(ScannerError::InvalidUnicodeEscape(
+                TokenPosition::new(1, 1, 2, 6, 1, 5),
+                "12".to_string()
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new(r#""\uD83Dx""#);
+        assert_eq!(
+            Err(ScannerError::UnpairedSurrogate(
+                TokenPosition::new(1, 1, 2, 8, 1, 7),
+                0xD83D
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new(r#""\uD83D\u0041""#);
+        assert_eq!(
+            Err(ScannerError::UnpairedSurrogate(
+                TokenPosition::new(1, 1, 2, 8, 1, 7),
+                0xD83D
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new(r#""\uDE00""#);
+        assert_eq!(
+            Err(ScannerError::UnpairedSurrogate(
+                TokenPosition::new(1, 1, 2, 8, 1, 7),
+                0xDE00
+            )),
+            s1.scan()
+        );
+
+        let s1 = Scanner::new(r#""abc\"#);
+        assert_eq!(
+            Err(ScannerError::UnterminatedString(TokenPosition::new(
+                1, 1, 1, 6, 0, 5
+            ))),
+            s1.scan()
+        );
+    }
+
+    #[test]
+    fn iterate_tokens() {
+        let mut s1 = Scanner::new("[true, @, null]");
+
+        assert_eq!(
+            Some(Ok(Token::new(
+                TokenType::LeftBracket,
+                TokenLiteral::String("[".to_string()),
+                TokenPosition::new(1, 1, 1, 2, 0, 1)
+            ))),
+            s1.next()
+        );
+        assert_eq!(TokenType::True, s1.next().unwrap().unwrap().token_type);
+        assert_eq!(5, s1.current);
+        assert_eq!(TokenType::Comma, s1.next().unwrap().unwrap().token_type);
+        assert_eq!(
+            Some(Err(ScannerError::UnknownCharacter(
+                TokenPosition::new(1, 1, 8, 9, 7, 8),
+                '@'
+            ))),
+            s1.next()
+        );
+        assert_eq!(None, s1.next());
+    }
+
+    #[test]
+    fn iterate_stop_early() {
+        let s1 = Scanner::new("[1, 2, 3, @]");
+        let numbers: Vec<String> = s1
+            .map(Result::unwrap)
+            .filter(|token| token.token_type == TokenType::Number)
+            .take(2)
+            .map(|token| token.token_literal.into())
+            .collect();
+
+        assert_eq!(vec!["1", "2"], numbers);
+    }
+
+    #[test]
+    fn scan_borrowed() {
+        let source = r#"{"key": "plain", "escaped": "a\nb", "n": 1.10}"#;
+        let tokens = Scanner::new(source).scan_borrowed().unwrap();
+
+        let BorrowedLiteral::String(Cow::Borrowed(plain)) = tokens[3].token_literal else {
+            panic!(
+                "expected a borrowed string, found {:?}",
+                tokens[3].token_literal
+            );
+        };
+        assert_eq!("plain", plain);
+        assert!(source.as_bytes().as_ptr_range().contains(&plain.as_ptr()));
+
+        assert_eq!(
+            BorrowedLiteral::String(Cow::Owned("a\nb".to_string())),
+            tokens[7].token_literal
+        );
+        assert_eq!(BorrowedLiteral::Number("1.10"), tokens[11].token_literal);
+        assert!(tokens[0].token_literal.is_borrowed());
+        assert_eq!(
+            Scanner::new(source).scan().unwrap(),
+            tokens
+                .into_iter()
+                .map(BorrowedToken::into_owned)
+                .collect::<Vec<Token>>()
+        );
+    }
+
+    #[test]
+    fn scan_recover() {
+        let config = ScannerConfig {
+            recover: true,
+            ..Default::default()
+        };
+
+        let s1 = Scanner::with_config("[tru, @\"a\\qb\\u12\", 01, 2]", config);
+        let (tokens, errors) = s1.scan_all();
+
+        assert_eq!(
+            vec![
+                TokenType::LeftBracket,
+                TokenType::Comma,
+                TokenType::Comma,
+                TokenType::Comma,
+                TokenType::Number,
+                TokenType::RightBracket,
+                TokenType::Eof,
+            ],
+            tokens
+                .into_iter()
+                .map(|token| token.token_type)
+                .collect::<Vec<TokenType>>()
+        );
+        assert_eq!(
+            vec![
+                ScannerError::UnknownLiteral(
+                    TokenPosition::new(1, 1, 2, 5, 1, 4),
+                    "tru".to_string(),
+                    Some(Box::new(Suggestion::new(
+                        TokenPosition::new(1, 1, 2, 5, 1, 4),
+                        "true",
+                        Applicability::MachineApplicable
+                    )))
+                ),
+                ScannerError::UnknownCharacter(TokenPosition::new(1, 1, 7, 8, 6, 7), '@'),
+                ScannerError::InvalidEscape(TokenPosition::new(1, 1, 10, 12, 9, 11), 'q'),
+                ScannerError::LeadingZero(
+                    TokenPosition::new(1, 1, 20, 22, 19, 21),
+                    "01".to_string()
+                ),
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn scan_recover_unterminated_string() {
+        let config = ScannerConfig {
+            recover: true,
+            ..Default::default()
+        };
+
+        let s1 = Scanner::with_config("[\"abc,\n  true]", config);
+        let (tokens, errors) = s1.scan_all();
+
+        assert_eq!(
+            vec![ScannerError::ControlCharacter(
+                TokenPosition::new(1, 1, 7, 8, 6, 7),
+                '\n'
+            )],
+            errors
+        );
+        assert_eq!(4, tokens.len());
+        assert_eq!(
+            TokenPosition::new(2, 2, 3, 7, 9, 13),
+            tokens[1].token_position
+        );
+
+        let (tokens, errors) = Scanner::new("[\"abc,\n  true]").scan_all();
+
+        assert_eq!(1, tokens.len());
+        assert_eq!(1, errors.len());
+    }
+
+    #[test]
+    fn scan_control_characters() {
+        assert_eq!(
+            Err(ScannerError::ControlCharacter(
+                TokenPosition::new(1, 1, 4, 5, 3, 4),
+                '\t'
+            )),
+            Scanner::new("\"ab\tc\"").scan()
+        );
+        assert_eq!(
+            Err(ScannerError::ControlCharacter(
+                TokenPosition::new(2, 2, 4, 5, 5, 6),
+                '\n'
+            )),
+            Scanner::new("[\n \"a\nb\"]").scan()
+        );
+        assert_eq!(
+            Err(ScannerError::ControlCharacter(
+                TokenPosition::new(1, 1, 2, 3, 1, 2),
+                '\u{0}'
+            )),
+            Scanner::new("\"\u{0}\"").scan()
+        );
+        assert!(Scanner::new("\"\\t\\n\u{7f}\"").scan().is_ok());
+
+        let json5 = ScannerConfig {
+            dialect: Dialect::Json5,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            TokenLiteral::String("a\tb".to_string()),
+            Scanner::with_config("'a\tb'", json5).scan().unwrap()[0].token_literal
+        );
+        assert_eq!(
+            Err(ScannerError::ControlCharacter(
+                TokenPosition::new(1, 1, 3, 4, 2, 3),
+                '\r'
+            )),
+            Scanner::with_config("'a\r\n'", json5).scan()
+        );
+    }
+
+    #[test]
+    fn scan_recover_control_characters() {
+        let config = ScannerConfig {
+            recover: true,
+            ..Default::default()
+        };
+
+        let (tokens, errors) =
+            Scanner::with_config("[\"a\tb\", \"c\n  \"d\", \"e\r\n  true]", config).scan_all();
+
+        assert_eq!(
+            vec![
+                ScannerError::ControlCharacter(TokenPosition::new(1, 1, 4, 5, 3, 4), '\t'),
+                ScannerError::ControlCharacter(TokenPosition::new(1, 1, 11, 12, 10, 11), '\n'),
+                ScannerError::ControlCharacter(TokenPosition::new(2, 2, 10, 11, 20, 21), '\r'),
+            ],
+            errors
+        );
+        assert_eq!(
+            vec![
+                (TokenType::LeftBracket, TokenPosition::new(1, 1, 1, 2, 0, 1)),
+                (TokenType::Comma, TokenPosition::new(1, 1, 7, 8, 6, 7)),
+                (TokenType::String, TokenPosition::new(2, 2, 3, 6, 13, 16)),
+                (TokenType::Comma, TokenPosition::new(2, 2, 6, 7, 16, 17)),
+                (TokenType::True, TokenPosition::new(3, 3, 3, 7, 24, 28)),
+                (
+                    TokenType::RightBracket,
+                    TokenPosition::new(3, 3, 7, 8, 28, 29)
+                ),
+                (TokenType::Eof, TokenPosition::new(3, 3, 8, 8, 29, 29)),
+            ],
+            tokens
+                .into_iter()
+                .map(|token| (token.token_type, token.token_position))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn update_column_start_and_end() {
+        let s1 = Scanner::new("{\n}");
+        let res = s1.scan().unwrap();
+
+        assert_eq!(
+            TokenPosition::new(1, 1, 1, 2, 0, 1),
+            res.first().unwrap().token_position
+        );
+        assert_eq!(
+            TokenPosition::new(2, 2, 1, 2, 2, 3),
+            res.get(1).unwrap().token_position
+        );
+    }
+
+    const JSON5: &str = "// config\n{\n  unquoted: 'single \"quoted\"',\n  $id_2: 0x1F, /* hex */\n  inf: -Infinity, nan: NaN,\n  points: [.5, 5., +1],\n  lines: 'a\\\n b',\n  \\u0061b: '\\x41\\v\\0',\n}\n";
+
+    fn json5() -> ScannerConfig {
+        ScannerConfig {
+            dialect: Dialect::Json5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scan_json5() {
+        let tokens = Scanner::with_config(JSON5, json5()).scan().unwrap();
+        let values: Vec<(TokenType, String)> = tokens
+            .into_iter()
+            .filter(|token| {
+                !matches!(
+                    token.token_type,
+                    TokenType::Colon | TokenType::Comma | TokenType::Eof
+                )
+            })
+            .map(|token| (token.token_type, token.token_literal.into()))
+            .collect();
+
+        let expected = [
+            (TokenType::Comment, "// config"),
+            (TokenType::LeftBrace, "{"),
+            (TokenType::Identifier, "unquoted"),
+            (TokenType::String, "single \"quoted\""),
+            (TokenType::Identifier, "$id_2"),
+            (TokenType::Number, "0x1F"),
+            (TokenType::Comment, "/* hex */"),
+            (TokenType::Identifier, "inf"),
+            (TokenType::Number, "-Infinity"),
+            (TokenType::Identifier, "nan"),
+            (TokenType::Number, "NaN"),
+            (TokenType::Identifier, "points"),
+            (TokenType::LeftBracket, "["),
+            (TokenType::Number, ".5"),
+            (TokenType::Number, "5."),
+            (TokenType::Number, "+1"),
+            (TokenType::RightBracket, "]"),
+            (TokenType::Identifier, "lines"),
+            (TokenType::String, "a b"),
+            (TokenType::Identifier, "ab"),
+            (TokenType::String, "A\u{b}\0"),
+            (TokenType::RightBrace, "}"),
+        ];
+
+        assert_eq!(
+            expected
+                .iter()
+                .map(|(token_type, literal)| (*token_type, literal.to_string()))
+                .collect::<Vec<_>>(),
+            values
+        );
+    }
+
+    #[test]
+    fn scan_json5_positions() {
+        let tokens = Scanner::with_config("/* a\n b */ x\n", json5())
+            .scan()
+            .unwrap();
+
+        assert_eq!(TokenType::Identifier, tokens[1].token_type);
+        assert_eq!(
+            TokenPosition::new(2, 2, 7, 8, 11, 12),
+            tokens[1].token_position
+        );
+
+        let tokens = Scanner::with_config("'a\\\r\nb' c", json5())
+            .scan()
+            .unwrap();
+
+        assert_eq!(
+            TokenLiteral::String("ab".to_string()),
+            tokens[0].token_literal
+        );
+        assert_eq!(
+            TokenPosition::new(1, 2, 1, 3, 0, 7),
+            tokens[0].token_position
+        );
+        assert_eq!(
+            TokenPosition::new(2, 2, 4, 5, 8, 9),
+            tokens[1].token_position
+        );
+
+        let tokens = Scanner::with_config("\u{feff}\u{a0}\u{2003}1", json5())
+            .scan()
+            .unwrap();
+
+        assert_eq!(2, tokens.len());
+    }
+
+    #[test]
+    fn scan_json5_errors() {
+        let cases = [
+            (
+                "/* open",
+                ScannerError::UnterminatedBlockComment(TokenPosition::new(1, 1, 1, 8, 0, 7)),
+            ),
+            (
+                "1 / 2",
+                ScannerError::UnknownCharacter(TokenPosition::new(1, 1, 3, 4, 2, 3), '/'),
+            ),
+            (
+                "0x",
+                ScannerError::MissingIntegerDigits(
+                    TokenPosition::new(1, 1, 1, 3, 0, 2),
+                    "0x".to_string(),
+                ),
+            ),
+            (
+                "-Infinite",
+                ScannerError::UnknownLiteral(
+                    TokenPosition::new(1, 1, 1, 10, 0, 9),
+                    "-Infinite".to_string(),
+                    Some(Box::new(Suggestion::new(
+                        TokenPosition::new(1, 1, 1, 10, 0, 9),
+                        "-Infinity",
+                        Applicability::MachineApplicable,
+                    ))),
+                ),
+            ),
+            (
+                ".",
+                ScannerError::MissingIntegerDigits(
+                    TokenPosition::new(1, 1, 1, 2, 0, 1),
+                    ".".to_string(),
+                ),
+            ),
+            (
+                "007",
+                ScannerError::LeadingZero(TokenPosition::new(1, 1, 1, 4, 0, 3), "007".to_string()),
+            ),
+            (
+                "a\\x",
+                ScannerError::InvalidEscape(TokenPosition::new(1, 1, 2, 4, 1, 3), 'x'),
+            ),
+            (
+                "'\\x4'",
+                ScannerError::InvalidEscape(TokenPosition::new(1, 1, 2, 5, 1, 4), 'x'),
+            ),
+            (
+                "'\\1'",
+                ScannerError::InvalidEscape(TokenPosition::new(1, 1, 2, 4, 1, 3), '1'),
+            ),
+            (
+                "'\\01'",
+                ScannerError::InvalidEscape(TokenPosition::new(1, 1, 2, 4, 1, 3), '0'),
+            ),
+        ];
+
+        for (source, error) in cases {
+            assert_eq!(
+                Err(error),
+                Scanner::with_config(source, json5()).scan(),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn scan_jsonc() {
+        let config = ScannerConfig {
+            dialect: Dialect::Jsonc,
+            ..Default::default()
+        };
+        let source = "{\n  // Editor\n  \"tabSize\": 2, /* spaces\n  */\n  \"files\": [\"a\",],\n}";
+        let tokens = Scanner::with_config(source, config).scan().unwrap();
+        let comments: Vec<(String, TokenPosition)> = tokens
+            .iter()
+            .filter(|token| token.token_type == TokenType::Comment)
+            .map(|token| (token.token_literal.clone().into(), token.token_position))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (
+                    "// Editor".to_string(),
+                    TokenPosition::new(2, 2, 3, 12, 4, 13)
+                ),
+                (
+                    "/* spaces\n  */".to_string(),
+                    TokenPosition::new(3, 4, 17, 5, 30, 44)
+                ),
+            ],
+            comments
+        );
+        assert_eq!(
+            vec![
+                TokenType::String,
+                TokenType::Comma,
+                TokenType::RightBracket,
+                TokenType::Comma,
+                TokenType::RightBrace,
+                TokenType::Eof,
+            ],
+            tokens[tokens.len() - 6..]
+                .iter()
+                .map(|token| token.token_type)
+                .collect::<Vec<TokenType>>()
+        );
+
+        assert_eq!(
+            Err(ScannerError::UnterminatedBlockComment(TokenPosition::new(
+                1, 2, 3, 3, 2, 9
+            ))),
+            Scanner::with_config("1 /* a\n *", config).scan()
+        );
+        assert_eq!(
+            "Unknown literal [key]",
+            Scanner::with_config("{key: 1}", config)
+                .scan()
+                .unwrap_err()
+                .to_string()
+                .split("] ")
+                .nth(1)
+                .unwrap()
+        );
+        assert!(Scanner::with_config("'a'", config).scan().is_err());
+    }
+
+    #[test]
+    fn json_rejects_json5() {
+        let cases = [
+            ("// comment", "Unknown character [/]"),
+            ("'a'", "Unknown character [']"),
+            ("key", "Unknown literal [key]"),
+            ("Infinity", "Unknown literal [Infinity]"),
+            ("0x1F", "Unknown literal [x]"),
+            ("+1", "Leading plus sign in number [+1]"),
+            (".5", "Missing integer digits in number [.5]"),
+            ("5.", "Missing fraction digits in number [5.]"),
+            ("\"\\v\"", "Invalid escape sequence [\\v]"),
+            ("\u{a0}", "Unknown character [\u{a0}]"),
+        ];
+
+        for (source, message) in cases {
+            let error = Scanner::new(source).scan().unwrap_err().to_string();
+
+            assert!(error.ends_with(message), "{}: {}", source, error);
+        }
+    }
+
+    fn trivia(dialect: Dialect) -> ScannerConfig {
+        ScannerConfig {
+            dialect,
+            trivia: true,
+            ..Default::default()
+        }
+    }
+
+    fn texts<'a>(trivia: &'a [Trivia]) -> Vec<(TriviaKind, &'a str)> {
+        trivia
+            .iter()
+            .map(|trivia| (trivia.kind, trivia.text.as_ref()))
+            .collect()
+    }
+
+    #[test]
+    fn scan_trivia_round_trip() {
+        let cases = [
+            (
+                Dialect::Json,
+                "{\r\n\t\"a\\u0041\" :  [ 1.50, true ,null ]\r\n}\r\n\n",
+            ),
+            (Dialect::Json, "  \n"),
+            (Dialect::Json, ""),
+            (
+                Dialect::Jsonc,
+                "// head\n{\n  \"a\": 1, // one\n  /* two */ \"b\": [2,],\n}\n/* tail */",
+            ),
+            (Dialect::Json5, JSON5),
+        ];
+
+        for (dialect, source) in cases {
+            let tokens = Scanner::with_config(source, trivia(dialect))
+                .scan_borrowed()
+                .unwrap();
+
+            assert!(tokens
+                .iter()
+                .all(|token| token.token_type != TokenType::Comment));
+
+            let round_trip: String = tokens
+                .iter()
+                .map(|token| token.trivia.as_ref().unwrap().to_string())
+                .collect();
+
+            assert_eq!(source, round_trip);
+        }
+    }
+
+    #[test]
+    fn scan_trivia_placement() {
+        let source = "{ // open\n  /* key */ \"a\": 1 ,\r\n\n  \"b\": 2\n}\n  ";
+        let tokens = Scanner::with_config(source, trivia(Dialect::Jsonc))
+            .scan()
+            .unwrap();
+
+        let brace = tokens[0].trivia.as_ref().unwrap();
+
+        assert!(brace.leading.is_empty());
+        assert_eq!("{", brace.lexeme);
+        assert_eq!(
+            vec![
+                (TriviaKind::Whitespace, " "),
+                (TriviaKind::Comment, "// open"),
+                (TriviaKind::Newline, "\n"),
+            ],
+            texts(&brace.trailing)
+        );
+        assert_eq!(
+            TokenPosition::new(1, 1, 3, 10, 2, 9),
+            brace.trailing[1].position
+        );
+
+        let key = tokens[1].trivia.as_ref().unwrap();
+
+        assert_eq!(
+            vec![
+                (TriviaKind::Whitespace, "  "),
+                (TriviaKind::Comment, "/* key */"),
+                (TriviaKind::Whitespace, " "),
+            ],
+            texts(&key.leading)
+        );
+        assert_eq!("\"a\"", key.lexeme);
+        assert!(key.trailing.is_empty());
+
+        let comma = tokens[4].trivia.as_ref().unwrap();
+
+        assert_eq!(vec![(TriviaKind::Newline, "\r\n")], texts(&comma.trailing));
+        assert_eq!(
+            TokenPosition::new(2, 3, 21, 1, 30, 32),
+            comma.trailing[0].position
+        );
+
+        let next_key = tokens[5].trivia.as_ref().unwrap();
+
+        assert_eq!(
+            vec![(TriviaKind::Newline, "\n"), (TriviaKind::Whitespace, "  ")],
+            texts(&next_key.leading)
+        );
+        assert_eq!(
+            TokenPosition::new(4, 4, 3, 6, 35, 38),
+            tokens[5].token_position
+        );
+
+        let eof = tokens.last().unwrap();
+
+        assert_eq!(TokenType::Eof, eof.token_type);
+        assert_eq!(
+            vec![(TriviaKind::Whitespace, "  ")],
+            texts(&eof.trivia.as_ref().unwrap().leading)
+        );
+        assert_eq!(
+            Scanner::new(
+                source
+                    .replace("// open", "")
+                    .replace("/* key */", "")
+                    .as_str()
+            )
+            .scan()
+            .unwrap()
+            .len(),
+            tokens.len()
+        );
+    }
+
+    #[test]
+    fn scan_trivia_errors() {
+        assert_eq!(
+            Err(ScannerError::UnterminatedBlockComment(TokenPosition::new(
+                1, 1, 3, 6, 2, 5
+            ))),
+            Scanner::with_config("1 /* ", trivia(Dialect::Jsonc)).scan()
+        );
+
+        let tokens = Scanner::new("[1]").scan().unwrap();
+
+        assert!(tokens.iter().all(|token| token.trivia.is_none()));
+    }
+
+    #[test]
+    fn scan_eof() {
+        let tokens = Scanner::new("[1]\n  ").scan().unwrap();
+
+        assert_eq!(4, tokens.len());
+        assert_eq!(TokenType::Eof, tokens[3].token_type);
+        assert_eq!(
+            TokenPosition::new(2, 2, 3, 3, 6, 6),
+            tokens[3].token_position
+        );
+
+        let tokens = Scanner::new("").scan().unwrap();
+
+        assert_eq!(1, tokens.len());
+        assert_eq!(TokenType::Eof, tokens[0].token_type);
+        assert_eq!(
+            TokenPosition::new(1, 1, 1, 1, 0, 0),
+            tokens[0].token_position
+        );
+
+        let mut s1 = Scanner::new("1");
+        assert!(s1.next().is_some());
+        assert!(s1.next().is_some());
+        assert!(s1.next().is_none());
+        assert!(s1.next().is_none());
+    }
+
+    #[test]
+    fn scan_empty_input() {
+        let config = ScannerConfig {
+            require_value: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Err(ScannerError::EmptyInput(TokenPosition::new(
+                1, 1, 1, 1, 0, 0
+            ))),
+            Scanner::with_config("", config).scan()
+        );
+        assert_eq!(
+            Err(ScannerError::EmptyInput(TokenPosition::new(
+                3, 3, 3, 3, 6, 6
+            ))),
+            Scanner::with_config(" \r\n\n\t ", config).scan()
+        );
+        assert_eq!(2, Scanner::with_config(" 1 ", config).scan().unwrap().len());
+
+        let config = ScannerConfig {
+            recover: true,
+            ..config
+        };
+        let (tokens, errors) = Scanner::with_config("\n", config).scan_all();
+
+        assert_eq!(
+            vec![ScannerError::EmptyInput(TokenPosition::new(
+                2, 2, 1, 1, 1, 1
+            ))],
+            errors
+        );
+        assert_eq!(TokenType::Eof, tokens[0].token_type);
+
+        let (tokens, errors) = Scanner::with_config("@", config).scan_all();
+
+        assert_eq!(1, errors.len());
+        assert_eq!(1, tokens.len());
+    }
+
+    #[test]
+    fn scan_error() {
+        let s1 = Scanner::new("@");
         let res = s1.scan();
 
         assert_eq!(
@@ -479,41 +2621,41 @@ mod scanner_tests {
     }
 
     #[test]
-    fn next() {
+    fn advance() {
         let mut s1 = Scanner::new("{}");
 
-        let first = s1.next();
+        let first = s1.advance();
 
         assert_eq!(Some('{'), first);
         assert_eq!(1, s1.current);
 
-        let second = s1.next();
+        let second = s1.advance();
 
         assert_eq!(Some('}'), second);
         assert_eq!(2, s1.current);
 
-        let none = s1.next();
+        let none = s1.advance();
         assert_eq!(None, none);
-        assert_eq!(3, s1.current);
+        assert_eq!(2, s1.current);
     }
 
     #[test]
-    fn next_to_end_of_source() {
+    fn advance_to_end_of_source() {
         let mut s1 = Scanner::new("true");
 
         let mut buf = String::new();
 
-        while let Some(current) = s1.next() {
+        while let Some(current) = s1.advance() {
             buf += &current.to_string();
         }
 
         assert_eq!("true", buf);
-        assert_eq!(5, s1.current);
+        assert_eq!(4, s1.current);
     }
 
     #[test]
     fn peek() {
-        let s1 = Scanner::new("true");
+        let mut s1 = Scanner::new("true");
 
         let peek = s1.peek();
 
@@ -532,4 +2674,209 @@ mod scanner_tests {
         assert_eq!(1, scanner.column_start);
         assert_eq!(1, scanner.column_end);
     }
+
+    #[test]
+    fn unknown_literal_suggestions() {
+        let json5 = ScannerConfig {
+            dialect: Dialect::Json5,
+            ..Default::default()
+        };
+
+        let cases = [
+            (
+                "tru",
+                Default::default(),
+                Some(("true", Applicability::MachineApplicable)),
+            ),
+            (
+                "Null",
+                Default::default(),
+                Some(("null", Applicability::MachineApplicable)),
+            ),
+            (
+                "TRUE",
+                Default::default(),
+                Some(("true", Applicability::MachineApplicable)),
+            ),
+            (
+                "nil",
+                Default::default(),
+                Some(("null", Applicability::MachineApplicable)),
+            ),
+            (
+                "None",
+                Default::default(),
+                Some(("null", Applicability::MachineApplicable)),
+            ),
+            (
+                "undefined",
+                Default::default(),
+                Some(("null", Applicability::MachineApplicable)),
+            ),
+            (
+                "flase",
+                Default::default(),
+                Some(("false", Applicability::MachineApplicable)),
+            ),
+            (
+                "fasle",
+                Default::default(),
+                Some(("false", Applicability::MachineApplicable)),
+            ),
+            (
+                "nulle",
+                Default::default(),
+                Some(("null", Applicability::MachineApplicable)),
+            ),
+            (
+                "trueee",
+                Default::default(),
+                Some(("true", Applicability::MaybeIncorrect)),
+            ),
+            ("xyz", Default::default(), None),
+            ("hello", Default::default(), None),
+            ("Infinity", Default::default(), None),
+            (
+                "-inf",
+                json5,
+                Some(("-Infinity", Applicability::MachineApplicable)),
+            ),
+            (
+                "+nan",
+                json5,
+                Some(("+NaN", Applicability::MachineApplicable)),
+            ),
+            ("-true", json5, None),
+        ];
+
+        for (source, config, expected) in cases {
+            let error = Scanner::with_config(source, config).scan().unwrap_err();
+
+            assert_eq!(
+                expected,
+                error
+                    .suggestion()
+                    .map(|suggestion| (suggestion.replacement.as_str(), suggestion.applicability)),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn scan_limits() {
+        let config = |limits: Limits| ScannerConfig {
+            recover: true,
+            limits,
+            ..Default::default()
+        };
+
+        let (tokens, errors) = Scanner::with_config(
+            r#"["abc", "abcd", 1]"#,
+            config(Limits {
+                max_string_length: 3,
+                ..Default::default()
+            }),
+        )
+        .scan_all();
+
+        assert_eq!(3, tokens.len());
+        assert_eq!(
+            vec![ScannerError::StringTooLong(
+                TokenPosition::new(1, 1, 9, 14, 8, 13),
+                3
+            )],
+            errors
+        );
+
+        let limits = Limits {
+            max_string_length: 4,
+            ..Default::default()
+        };
+        assert!(Scanner::with_config(r#""\u00e9\u00e9""#, config(limits))
+            .scan()
+            .is_ok());
+        assert_eq!(
+            Err(ScannerError::StringTooLong(
+                TokenPosition::new(1, 1, 1, 7, 0, 6),
+                4
+            )),
+            Scanner::with_config(r#""abcde and never closed"#, config(limits)).scan()
+        );
+
+        let cases = [
+            (
+                "[12345]",
+                Limits {
+                    max_number_length: 4,
+                    ..Default::default()
+                },
+                ScannerError::NumberTooLong(TokenPosition::new(1, 1, 2, 7, 1, 6), 4),
+            ),
+            (
+                "[nulll]",
+                Limits {
+                    max_literal_length: 4,
+                    ..Default::default()
+                },
+                ScannerError::LiteralTooLong(TokenPosition::new(1, 1, 2, 7, 1, 6), 4),
+            ),
+            (
+                "[1, 2]",
+                Limits {
+                    max_tokens: 3,
+                    ..Default::default()
+                },
+                ScannerError::TooManyTokens(TokenPosition::new(1, 1, 5, 6, 4, 5), 3),
+            ),
+            (
+                "[1, 2]",
+                Limits {
+                    max_document_size: 4,
+                    ..Default::default()
+                },
+                ScannerError::DocumentTooLarge(TokenPosition::new(1, 1, 5, 6, 4, 5), 4),
+            ),
+            (
+                "[1]   ",
+                Limits {
+                    max_document_size: 3,
+                    ..Default::default()
+                },
+                ScannerError::DocumentTooLarge(TokenPosition::new(1, 1, 5, 5, 4, 4), 3),
+            ),
+        ];
+
+        for (source, limits, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                Scanner::with_config(source, config(limits)).scan(),
+                "{}",
+                source
+            );
+        }
+
+        let json5 = ScannerConfig {
+            dialect: Dialect::Json5,
+            limits: Limits {
+                max_literal_length: 4,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(ScannerError::LiteralTooLong(
+                TokenPosition::new(1, 1, 2, 7, 1, 6),
+                4
+            )),
+            Scanner::with_config("[-Infinity]", json5).scan()
+        );
+
+        let limits = Limits {
+            max_document_size: 3,
+            max_tokens: 3,
+            ..Default::default()
+        };
+        assert!(Scanner::with_config("[1]", config(limits)).scan().is_ok());
+    }
 }
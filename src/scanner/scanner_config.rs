@@ -0,0 +1,48 @@
+use crate::source_map::column_unit::ColumnUnit;
+
+use super::{dialect::Dialect, limits::Limits};
+
+///Options controlling how a [`Scanner`](super::scanner::Scanner) reads its input
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::{scanner::Scanner, scanner_config::ScannerConfig};
+///let config = ScannerConfig {
+///    recover: true,
+///    ..Default::default()
+///};
+///
+///let (tokens, errors) = Scanner::with_config("[1, @, 2]", config).scan_all();
+///
+///assert_eq!(7, tokens.len());
+///assert_eq!(1, errors.len());
+///```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct ScannerConfig {
+    ///Keep scanning after an error instead of stopping at the first one
+    ///
+    ///The scanner resynchronises after each error: unknown characters and literals are skipped,
+    ///a string with a bad escape or control character is skipped up to its closing quote and a
+    ///string is abandoned at the first raw line ending inside it.
+    pub recover: bool,
+    ///Which extensions to JSON are accepted, strict JSON by default
+    pub dialect: Dialect,
+    ///Unit the columns of every reported position are counted in
+    ///
+    ///Defaults to Unicode scalar values. Use [`ColumnUnit::Utf16`] for LSP clients and
+    ///[`ColumnUnit::Display`] to line positions up with what a terminal shows.
+    pub column_unit: ColumnUnit,
+    ///Report input that is empty or only whitespace as [`ScannerError::EmptyInput`]
+    ///
+    ///[`ScannerError::EmptyInput`]: super::scanner_error::ScannerError::EmptyInput
+    pub require_value: bool,
+    ///Keep whitespace and comments as [`Token::trivia`] instead of dropping them
+    ///
+    ///Comments are attached to tokens as trivia rather than scanned as comment tokens.
+    ///
+    ///[`Token::trivia`]: crate::token::token::Token::trivia
+    pub trivia: bool,
+    ///Bounds on the size of the input, see [`Limits`]
+    pub limits: Limits,
+}
@@ -1,100 +1,465 @@
 use std::{error::Error, fmt::Display};
 
-use crate::token::token_position::TokenPosition;
+use crate::{diagnostic::suggestion::Suggestion, token::token_position::TokenPosition};
+
+use super::encoding::Encoding;
 
 #[derive(Debug, PartialEq)]
 pub enum ScannerError {
     UnknownCharacter(TokenPosition, char),
-    UnknownLiteral(TokenPosition, String),
+    UnknownLiteral(TokenPosition, String, Option<Box<Suggestion>>),
     UnterminatedString(TokenPosition),
+    ControlCharacter(TokenPosition, char),
+    UnterminatedBlockComment(TokenPosition),
+    InvalidEscape(TokenPosition, char),
+    InvalidUnicodeEscape(TokenPosition, String),
+    UnpairedSurrogate(TokenPosition, u16),
+    LeadingPlusSign(TokenPosition, String),
+    LeadingZero(TokenPosition, String),
+    MissingIntegerDigits(TokenPosition, String),
+    MissingFractionDigits(TokenPosition, String),
+    MissingExponentDigits(TokenPosition, String),
+    InvalidUtf8(TokenPosition),
+    Io(TokenPosition, String),
+    EmptyInput(TokenPosition),
+    InvalidEncoding(TokenPosition, Encoding),
+    DocumentTooLarge(TokenPosition, usize),
+    StringTooLong(TokenPosition, usize),
+    NumberTooLong(TokenPosition, usize),
+    TooManyTokens(TokenPosition, usize),
+    LiteralTooLong(TokenPosition, usize),
 }
 
-impl Display for ScannerError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ScannerError {
+    ///Where in the source the error was found
+    pub fn position(&self) -> &TokenPosition {
         match self {
-            Self::UnknownCharacter(
-                TokenPosition {
-                    line,
-                    column_start,
-                    column_end,
-                    ..
-                },
-                character,
-            ) => write!(
-                f,
-                "Error at [line:{}, between:{}-{}] Unknown character [{}]",
-                line, column_start, column_end, character,
-            ),
-            Self::UnknownLiteral(
-                TokenPosition {
-                    line,
-                    column_start,
-                    column_end,
-                    ..
-                },
-                content,
-            ) => write!(
-                f,
-                "Error at [line:{}, between:{}-{}] Unknown literal [{}]",
-                line, column_start, column_end, content,
-            ),
-            Self::UnterminatedString(TokenPosition {
-                line,
-                column_start,
-                column_end,
-                ..
-            }) => write!(
-                f,
-                "Error at [line:{}, between:{}-{}] Unterminated string",
-                line, column_start, column_end,
+            Self::UnknownCharacter(position, _)
+            | Self::UnknownLiteral(position, ..)
+            | Self::UnterminatedString(position)
+            | Self::ControlCharacter(position, _)
+            | Self::UnterminatedBlockComment(position)
+            | Self::InvalidEscape(position, _)
+            | Self::InvalidUnicodeEscape(position, _)
+            | Self::UnpairedSurrogate(position, _)
+            | Self::LeadingPlusSign(position, _)
+            | Self::LeadingZero(position, _)
+            | Self::MissingIntegerDigits(position, _)
+            | Self::MissingFractionDigits(position, _)
+            | Self::MissingExponentDigits(position, _)
+            | Self::InvalidUtf8(position)
+            | Self::Io(position, _)
+            | Self::EmptyInput(position)
+            | Self::InvalidEncoding(position, _)
+            | Self::DocumentTooLarge(position, _)
+            | Self::StringTooLong(position, _)
+            | Self::NumberTooLong(position, _)
+            | Self::TooManyTokens(position, _)
+            | Self::LiteralTooLong(position, _) => position,
+        }
+    }
+
+    ///Stable code identifying the kind of error
+    ///
+    ///Codes never change meaning once released, unlike messages which may be reworded. Scanner
+    ///errors use `J0001`-`J0099`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownCharacter(..) => "J0001",
+            Self::UnknownLiteral(..) => "J0002",
+            Self::UnterminatedString(..) => "J0003",
+            Self::ControlCharacter(..) => "J0004",
+            Self::UnterminatedBlockComment(..) => "J0005",
+            Self::InvalidEscape(..) => "J0006",
+            Self::InvalidUnicodeEscape(..) => "J0007",
+            Self::UnpairedSurrogate(..) => "J0008",
+            Self::LeadingPlusSign(..) => "J0009",
+            Self::LeadingZero(..) => "J0010",
+            Self::MissingIntegerDigits(..) => "J0011",
+            Self::MissingFractionDigits(..) => "J0012",
+            Self::MissingExponentDigits(..) => "J0013",
+            Self::InvalidUtf8(..) => "J0014",
+            Self::Io(..) => "J0015",
+            Self::EmptyInput(..) => "J0016",
+            Self::InvalidEncoding(..) => "J0017",
+            Self::DocumentTooLarge(..) => "J0018",
+            Self::StringTooLong(..) => "J0019",
+            Self::NumberTooLong(..) => "J0020",
+            Self::TooManyTokens(..) => "J0021",
+            Self::LiteralTooLong(..) => "J0022",
+        }
+    }
+
+    ///Whether the error is a [`Limits`](super::limits::Limits) being exceeded, after which
+    ///scanning always stops
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(
+            self,
+            Self::DocumentTooLarge(..)
+                | Self::StringTooLong(..)
+                | Self::NumberTooLong(..)
+                | Self::TooManyTokens(..)
+                | Self::LiteralTooLong(..)
+        )
+    }
+
+    ///Fix proposed for the error, if one is known
+    pub fn suggestion(&self) -> Option<&Suggestion> {
+        match self {
+            Self::UnknownLiteral(_, _, suggestion) => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+
+    ///Describe the error without its position
+    pub fn message(&self) -> String {
+        match self {
+            Self::UnknownCharacter(_, character) => format!("Unknown character [{}]", character),
+            Self::UnknownLiteral(_, content, _) => format!("Unknown literal [{}]", content),
+            Self::UnterminatedString(_) => "Unterminated string".to_string(),
+            Self::ControlCharacter(_, character) => format!(
+                "Unescaped control character [U+{:04X}] in string",
+                u32::from(*character)
             ),
+            Self::UnterminatedBlockComment(_) => "Unterminated block comment".to_string(),
+            Self::InvalidEscape(_, character) => {
+                format!("Invalid escape sequence [\\{}]", character)
+            }
+            Self::InvalidUnicodeEscape(_, digits) => {
+                format!("Invalid unicode escape [\\u{}]", digits)
+            }
+            Self::UnpairedSurrogate(_, surrogate) => {
+                format!("Unpaired surrogate [\\u{:04X}]", surrogate)
+            }
+            Self::LeadingPlusSign(_, number) => {
+                format!("Leading plus sign in number [{}]", number)
+            }
+            Self::LeadingZero(_, number) => format!("Leading zero in number [{}]", number),
+            Self::MissingIntegerDigits(_, number) => {
+                format!("Missing integer digits in number [{}]", number)
+            }
+            Self::MissingFractionDigits(_, number) => {
+                format!("Missing fraction digits in number [{}]", number)
+            }
+            Self::MissingExponentDigits(_, number) => {
+                format!("Missing exponent digits in number [{}]", number)
+            }
+            Self::InvalidUtf8(_) => "Invalid UTF-8".to_string(),
+            Self::Io(_, message) => format!("Failed to read input [{}]", message),
+            Self::EmptyInput(_) => "Expected a value but found end of input".to_string(),
+            Self::InvalidEncoding(_, encoding) => format!("Invalid {}", encoding),
+            Self::DocumentTooLarge(_, limit) => {
+                format!("Document larger than the limit of {} bytes", limit)
+            }
+            Self::StringTooLong(_, limit) => {
+                format!("String longer than the limit of {} bytes", limit)
+            }
+            Self::NumberTooLong(_, limit) => {
+                format!("Number longer than the limit of {} bytes", limit)
+            }
+            Self::TooManyTokens(_, limit) => format!("More than the limit of {} tokens", limit),
+            Self::LiteralTooLong(_, limit) => {
+                format!("Literal longer than the limit of {} bytes", limit)
+            }
         }
     }
+
+    ///Short description of what is wrong with the text under the error's span
+    pub fn label(&self) -> Option<String> {
+        let label = match self {
+            Self::UnknownCharacter(..) => "not valid here",
+            Self::UnknownLiteral(..) => "not a JSON value",
+            Self::UnterminatedString(..) => "missing a closing quote",
+            Self::ControlCharacter(..) => "must be escaped",
+            Self::UnterminatedBlockComment(..) => "never closed",
+            Self::InvalidEscape(..) => "unknown escape sequence",
+            Self::InvalidUnicodeEscape(..) => "expected four hex digits",
+            Self::UnpairedSurrogate(..) => "half of a surrogate pair",
+            Self::LeadingPlusSign(..) => "starts with `+`",
+            Self::LeadingZero(..) => "starts with a zero",
+            Self::MissingIntegerDigits(..) => "no digits before the fraction",
+            Self::MissingFractionDigits(..) => "no digits after the decimal point",
+            Self::MissingExponentDigits(..) => "no digits in the exponent",
+            Self::InvalidUtf8(..) => "not UTF-8",
+            Self::Io(..) => return None,
+            Self::EmptyInput(..) => "expected a value",
+            Self::InvalidEncoding(_, encoding) => return Some(format!("not {}", encoding)),
+            Self::DocumentTooLarge(..) => "limit reached here",
+            Self::StringTooLong(..) | Self::NumberTooLong(..) | Self::LiteralTooLong(..) => {
+                "too long"
+            }
+            Self::TooManyTokens(..) => "limit reached here",
+        };
+
+        Some(label.to_string())
+    }
+
+    ///Notes on how to fix the error
+    pub fn help(&self) -> Vec<String> {
+        let help = match self {
+            Self::UnknownLiteral(_, _, Some(suggestion)) => {
+                format!("did you mean `{}`?", suggestion.replacement)
+            }
+            Self::UnknownLiteral(..) => "strings must be quoted e.g. `\"text\"`".to_string(),
+            Self::UnterminatedString(..) => "add a `\"` where the string should end".to_string(),
+            Self::ControlCharacter(_, character) => {
+                format!("write it as `{}`", escape_control_character(*character))
+            }
+            Self::UnterminatedBlockComment(..) => "close the comment with `*/`".to_string(),
+            Self::InvalidEscape(..) => {
+                "valid escapes are `\\\"`, `\\\\`, `\\/`, `\\b`, `\\f`, `\\n`, `\\r`, `\\t` and `\\uXXXX`"
+                    .to_string()
+            }
+            Self::UnpairedSurrogate(..) => {
+                "a high surrogate `\\uD800`-`\\uDBFF` must be followed by a low surrogate `\\uDC00`-`\\uDFFF`"
+                    .to_string()
+            }
+            Self::LeadingPlusSign(..) => "remove the `+`".to_string(),
+            Self::LeadingZero(..) => "remove the leading zeros".to_string(),
+            Self::MissingIntegerDigits(..) => "add a `0` before the decimal point".to_string(),
+            Self::MissingFractionDigits(..) => {
+                "add digits after the decimal point or remove it".to_string()
+            }
+            Self::MissingExponentDigits(..) => "add digits after the exponent".to_string(),
+            Self::EmptyInput(..) => "a JSON document must contain a value".to_string(),
+            Self::DocumentTooLarge(..) => limit_help("max_document_size"),
+            Self::StringTooLong(..) => limit_help("max_string_length"),
+            Self::NumberTooLong(..) => limit_help("max_number_length"),
+            Self::TooManyTokens(..) => limit_help("max_tokens"),
+            Self::LiteralTooLong(..) => limit_help("max_literal_length"),
+            _ => return vec![],
+        };
+
+        vec![help]
+    }
+}
+
+impl Display for ScannerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let TokenPosition {
+            line_start: line,
+            column_start,
+            column_end,
+            ..
+        } = self.position();
+
+        write!(
+            f,
+            "Error at [line:{}, between:{}-{}] {}",
+            line,
+            column_start,
+            column_end,
+            self.message()
+        )
+    }
 }
 
 impl Error for ScannerError {}
 
+///Help for a limit that was exceeded, pointing at the field that sets it
+pub(crate) fn limit_help(field: &str) -> String {
+    format!("raise `Limits::{}` if the input is trusted", field)
+}
+
+///The escape sequence JSON uses for a control character
+fn escape_control_character(character: char) -> String {
+    match character {
+        '\u{8}' => "\\b".to_string(),
+        '\u{c}' => "\\f".to_string(),
+        '\n' => "\\n".to_string(),
+        '\r' => "\\r".to_string(),
+        '\t' => "\\t".to_string(),
+        _ => format!("\\u{:04X}", u32::from(character)),
+    }
+}
+
 #[cfg(test)]
-mod scanner_error {
-    use crate::token::token_position::TokenPosition;
+mod scanner_error_tests {
+    use crate::{scanner::encoding::Encoding, token::token_position::TokenPosition};
 
     use super::ScannerError;
 
     #[test]
     fn unknown_character() {
-        let unknown: Result<&str, ScannerError> = Err(ScannerError::UnknownCharacter(
-            TokenPosition::new(1, 1, 2),
-            '@',
-        ));
+        let unknown = ScannerError::UnknownCharacter(TokenPosition::new(1, 1, 1, 2, 0, 1), '@');
 
         assert_eq!(
             "Error at [line:1, between:1-2] Unknown character [@]",
-            unknown.unwrap_err().to_string()
+            unknown.to_string()
         );
     }
 
     #[test]
     fn unknown_literal() {
-        let unknown_literal: Result<&str, ScannerError> = Err(ScannerError::UnknownLiteral(
-            TokenPosition::new(1, 1, 6),
+        let unknown_literal = ScannerError::UnknownLiteral(
+            TokenPosition::new(1, 1, 1, 6, 0, 5),
             "hello".to_string(),
-        ));
+            None,
+        );
 
         assert_eq!(
             "Error at [line:1, between:1-6] Unknown literal [hello]",
-            unknown_literal.unwrap_err().to_string()
+            unknown_literal.to_string()
         );
     }
 
     #[test]
     fn unterminated_string() {
-        let unterminated: Result<&str, ScannerError> = Err(ScannerError::UnterminatedString(
-            TokenPosition::new(1, 1, 6),
-        ));
+        let unterminated = ScannerError::UnterminatedString(TokenPosition::new(1, 1, 1, 6, 0, 5));
 
         assert_eq!(
             "Error at [line:1, between:1-6] Unterminated string",
-            unterminated.unwrap_err().to_string()
+            unterminated.to_string()
+        );
+    }
+
+    #[test]
+    fn control_character() {
+        let control = ScannerError::ControlCharacter(TokenPosition::new(1, 1, 5, 6, 4, 5), '\n');
+
+        assert_eq!(
+            "Error at [line:1, between:5-6] Unescaped control character [U+000A] in string",
+            control.to_string()
+        );
+        assert_eq!(&TokenPosition::new(1, 1, 5, 6, 4, 5), control.position());
+    }
+
+    #[test]
+    fn unterminated_block_comment() {
+        let unterminated =
+            ScannerError::UnterminatedBlockComment(TokenPosition::new(1, 2, 3, 4, 2, 9));
+
+        assert_eq!(
+            "Error at [line:1, between:3-4] Unterminated block comment",
+            unterminated.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_escape() {
+        let invalid = ScannerError::InvalidEscape(TokenPosition::new(1, 1, 3, 5, 2, 4), 'q');
+
+        assert_eq!(
+            "Error at [line:1, between:3-5] Invalid escape sequence [\\q]",
+            invalid.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_unicode_escape() {
+        let invalid = ScannerError::InvalidUnicodeEscape(
+            TokenPosition::new(1, 1, 2, 6, 1, 5),
+            "12".to_string(),
+        );
+
+        assert_eq!(
+            "Error at [line:1, between:2-6] Invalid unicode escape [\\u12]",
+            invalid.to_string()
         );
     }
+
+    #[test]
+    fn unpaired_surrogate() {
+        let unpaired =
+            ScannerError::UnpairedSurrogate(TokenPosition::new(1, 1, 2, 8, 1, 7), 0xD83D);
+
+        assert_eq!(
+            "Error at [line:1, between:2-8] Unpaired surrogate [\\uD83D]",
+            unpaired.to_string()
+        );
+    }
+
+    #[test]
+    fn malformed_number() {
+        let leading_zero =
+            ScannerError::LeadingZero(TokenPosition::new(1, 1, 1, 4, 0, 3), "012".to_string());
+
+        assert_eq!(
+            "Error at [line:1, between:1-4] Leading zero in number [012]",
+            leading_zero.to_string()
+        );
+
+        let missing_exponent = ScannerError::MissingExponentDigits(
+            TokenPosition::new(2, 2, 5, 8, 4, 7),
+            "1e+".to_string(),
+        );
+
+        assert_eq!(
+            "Error at [line:2, between:5-8] Missing exponent digits in number [1e+]",
+            missing_exponent.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let invalid = ScannerError::InvalidUtf8(TokenPosition::new(2, 2, 4, 5, 3, 4));
+
+        assert_eq!(
+            "Error at [line:2, between:4-5] Invalid UTF-8",
+            invalid.to_string()
+        );
+    }
+
+    #[test]
+    fn empty_input() {
+        let empty = ScannerError::EmptyInput(TokenPosition::new(2, 2, 3, 3, 4, 4));
+
+        assert_eq!(
+            "Error at [line:2, between:3-3] Expected a value but found end of input",
+            empty.to_string()
+        );
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let too_long = ScannerError::StringTooLong(TokenPosition::new(1, 1, 2, 9, 1, 8), 4);
+
+        assert_eq!(
+            "Error at [line:1, between:2-9] String longer than the limit of 4 bytes",
+            too_long.to_string()
+        );
+        assert_eq!(
+            vec!["raise `Limits::max_string_length` if the input is trusted".to_string()],
+            too_long.help()
+        );
+        assert!(too_long.is_limit_exceeded());
+        assert!(
+            !ScannerError::EmptyInput(TokenPosition::new(1, 1, 1, 1, 0, 0)).is_limit_exceeded()
+        );
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        let position = TokenPosition::new(1, 1, 1, 2, 0, 1);
+        let errors = [
+            ScannerError::UnknownCharacter(position, '@'),
+            ScannerError::UnknownLiteral(position, "x".to_string(), None),
+            ScannerError::UnterminatedString(position),
+            ScannerError::ControlCharacter(position, '\n'),
+            ScannerError::UnterminatedBlockComment(position),
+            ScannerError::InvalidEscape(position, 'x'),
+            ScannerError::InvalidUnicodeEscape(position, "zz".to_string()),
+            ScannerError::UnpairedSurrogate(position, 0xd800),
+            ScannerError::LeadingPlusSign(position, "+1".to_string()),
+            ScannerError::LeadingZero(position, "01".to_string()),
+            ScannerError::MissingIntegerDigits(position, ".1".to_string()),
+            ScannerError::MissingFractionDigits(position, "1.".to_string()),
+            ScannerError::MissingExponentDigits(position, "1e".to_string()),
+            ScannerError::InvalidUtf8(position),
+            ScannerError::Io(position, "gone".to_string()),
+            ScannerError::EmptyInput(position),
+            ScannerError::InvalidEncoding(position, Encoding::Utf16Le),
+            ScannerError::DocumentTooLarge(position, 1),
+            ScannerError::StringTooLong(position, 1),
+            ScannerError::NumberTooLong(position, 1),
+            ScannerError::TooManyTokens(position, 1),
+            ScannerError::LiteralTooLong(position, 1),
+        ];
+
+        let codes: Vec<_> = errors.iter().map(ScannerError::code).collect();
+
+        for (index, code) in codes.iter().enumerate() {
+            assert_eq!(format!("J{:04}", index + 1), *code);
+        }
+    }
 }
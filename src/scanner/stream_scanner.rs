@@ -0,0 +1,629 @@
+use std::io::Read;
+
+use crate::{
+    source_map::column_unit::ColumnCounter,
+    token::{token::Token, token_position::TokenPosition, token_type::TokenType},
+};
+
+use super::{
+    encoding::Encoding, scanner::Scanner, scanner_config::ScannerConfig,
+    scanner_error::ScannerError,
+};
+
+///Default number of bytes requested from the reader per read
+pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+///Scan tokens from any [`Read`] without holding the whole document in memory
+///
+///Input is read in fixed-size chunks and decoded into a small text buffer which the regular
+///[`Scanner`] runs over. A token is only handed out once the character after it has been seen (or
+///the reader is exhausted), so numbers, keywords, strings, escape sequences and multi-byte UTF-8
+///characters that are split across chunks are rescanned once more input arrives. Only the longest
+///token ever needs to fit in memory.
+///
+///The encoding is detected from the first bytes with [`Encoding::detect`]. UTF-16 and UTF-32 input
+///is transcoded as it is read, in which case offsets count bytes of the transcoded UTF-8 text.
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::stream_scanner::StreamScanner;
+///let input = std::io::Cursor::new(r#"{"name": "jtool"}"#);
+///let tokens = StreamScanner::new(input).scan().unwrap();
+///
+///assert_eq!(6, tokens.len());
+///```
+#[derive(Debug)]
+pub struct StreamScanner<R: Read> {
+    reader: R,
+    pub chunk_size: usize,
+    pub config: ScannerConfig,
+    bytes: Vec<u8>,
+    buffer: String,
+    consumed: usize,
+    offset: usize,
+    line: usize,
+    column: usize,
+    columns: ColumnCounter,
+    empty: bool,
+    token_count: usize,
+    encoding: Option<Encoding>,
+    eof: bool,
+    invalid_input: bool,
+    done: bool,
+}
+
+impl<R: Read> StreamScanner<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_chunk_size(reader, DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(reader: R, chunk_size: usize) -> Self {
+        Self {
+            reader,
+            chunk_size,
+            config: ScannerConfig::default(),
+            bytes: vec![],
+            buffer: String::new(),
+            consumed: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+            columns: ColumnCounter::new(),
+            empty: true,
+            token_count: 0,
+            encoding: None,
+            eof: false,
+            invalid_input: false,
+            done: false,
+        }
+    }
+
+    pub fn with_config(reader: R, config: ScannerConfig) -> Self {
+        Self {
+            config,
+            ..Self::new(reader)
+        }
+    }
+
+    ///Scan the whole stream, stopping at the first error
+    pub fn scan(self) -> Result<Vec<Token>, ScannerError> {
+        self.collect()
+    }
+
+    ///Scan the whole stream returning every token and every error found
+    ///
+    ///Only stops at the first error unless [`ScannerConfig::recover`] is set.
+    pub fn scan_all(self) -> (Vec<Token>, Vec<ScannerError>) {
+        let mut tokens = vec![];
+        let mut errors = vec![];
+
+        for res in self {
+            match res {
+                Ok(token) => tokens.push(token),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        (tokens, errors)
+    }
+
+    ///Read chunks until at least `target` bytes of text are buffered or the reader is exhausted
+    ///
+    ///Stops reading one byte past [`Limits::max_document_size`](super::limits::Limits), which is
+    ///enough to tell that the document is too large.
+    fn fill(&mut self, target: usize) -> Result<(), ScannerError> {
+        self.buffer.drain(..self.offset);
+        self.consumed += self.offset;
+        self.offset = 0;
+
+        let mut chunk = vec![0; self.chunk_size.max(1)];
+        let limit = self.config.limits.max_document_size.saturating_add(1);
+
+        while self.buffer.len() < target && !self.eof && !self.invalid_input {
+            let budget = limit.saturating_sub(self.read_size()).min(chunk.len());
+
+            if budget == 0 {
+                break;
+            }
+
+            let read = match self.reader.read(&mut chunk[..budget]) {
+                Ok(read) => read,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    return Err(ScannerError::Io(self.end_position(), error.to_string()));
+                }
+            };
+
+            if read == 0 {
+                self.eof = true;
+            } else {
+                self.bytes.extend_from_slice(&chunk[..read]);
+            }
+
+            self.decode();
+            self.invalid_input |= self.eof && !self.bytes.is_empty();
+        }
+
+        Ok(())
+    }
+
+    ///Move every complete character from `bytes` into `buffer`, leaving a trailing partial
+    ///character behind for the next read
+    ///
+    ///The encoding is detected once four bytes, enough to tell every encoding apart, have been
+    ///read.
+    fn decode(&mut self) {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None if self.bytes.len() >= 4 || self.eof => {
+                *self.encoding.insert(Encoding::detect(&self.bytes))
+            }
+            None => return,
+        };
+
+        let decoded = encoding
+            .decode_into(&self.bytes, &mut self.buffer)
+            .unwrap_or_else(|decoded| {
+                self.invalid_input = true;
+                decoded
+            });
+
+        self.bytes.drain(..decoded);
+    }
+
+    ///Bytes read so far, decoded or not
+    fn read_size(&self) -> usize {
+        self.consumed + self.buffer.len() + self.bytes.len()
+    }
+
+    ///Position of the byte just past all the buffered text, which is where the stream stopped
+    fn end_position(&self) -> TokenPosition {
+        let offset = self.consumed + self.buffer.len();
+        let mut line = self.line;
+        let mut column = self.column;
+        let mut columns = self.columns;
+
+        for char in self.buffer[self.offset..].chars() {
+            if char == '\n' {
+                line += 1;
+                column = 1;
+                columns.reset();
+            } else {
+                column = columns.advance(self.config.column_unit, column, char);
+            }
+        }
+
+        TokenPosition::new(line, line, column, column + 1, offset, offset + 1)
+    }
+}
+
+impl<R: Read> Iterator for StreamScanner<R> {
+    type Item = Result<Token, ScannerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let pending = &self.buffer[self.offset..];
+
+            let mut scanner = Scanner::with_config(pending, self.config);
+            scanner.base_offset = self.consumed + self.offset;
+            scanner.line_start = self.line;
+            scanner.line = self.line;
+            scanner.column_start = self.column;
+            scanner.column_end = self.column;
+            scanner.columns = self.columns;
+            scanner.empty = self.empty;
+            scanner.token_count = self.token_count;
+
+            let start = scanner.cursor();
+            let item = scanner.next();
+
+            if scanner.exhausted {
+                let limit = self.config.limits.max_document_size;
+
+                //More input only makes the token longer, a limit it exceeds stays exceeded
+                if matches!(&item, Some(Err(error)) if error.is_limit_exceeded()) {
+                    self.done = true;
+                    return item;
+                }
+
+                if !self.eof && !self.invalid_input && self.read_size() > limit {
+                    self.done = true;
+                    return Some(Err(ScannerError::DocumentTooLarge(
+                        scanner.position_from(start),
+                        limit,
+                    )));
+                }
+
+                if !self.eof && !self.invalid_input {
+                    let target = (self.buffer.len() - self.offset) * 2 + self.chunk_size;
+
+                    if let Err(error) = self.fill(target) {
+                        self.done = true;
+                        return Some(Err(error));
+                    }
+
+                    continue;
+                }
+
+                if self.invalid_input {
+                    self.done = true;
+                    let encoding = self.encoding.unwrap_or_default();
+                    return Some(Err(encoding.error(self.end_position())));
+                }
+            }
+
+            self.offset += scanner.current.min(pending.len());
+            self.line = scanner.line;
+            self.column = scanner.column_end;
+            self.columns = scanner.columns;
+            self.empty = scanner.empty;
+            self.token_count = scanner.token_count;
+
+            match &item {
+                Some(Ok(token)) if token.token_type != TokenType::Eof => {}
+                Some(Err(error)) if self.config.recover && !error.is_limit_exceeded() => {}
+                _ => self.done = true,
+            }
+
+            return item;
+        }
+    }
+}
+
+#[cfg(test)]
+mod stream_scanner_tests {
+    use std::io::{Cursor, Read};
+
+    use crate::{
+        scanner::{
+            dialect::Dialect, encoding::Encoding, limits::Limits, scanner::Scanner,
+            scanner_config::ScannerConfig, scanner_error::ScannerError,
+        },
+        source_map::column_unit::ColumnUnit,
+        token::token_position::TokenPosition,
+    };
+
+    use super::StreamScanner;
+
+    const SOURCE: &str = "{\n  \"name\": \"caf\u{e9} \\u00e9 \\uD83D\\uDE00 😀\",\n  \"id\": 9007199254740993,\n  \"ratio\": -2.5E-3,\n  \"tags\": [true, false, null]\n}\n";
+
+    ///Reader handing out at most `step` bytes per read
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.step.min(buf.len()).min(self.bytes.len());
+            buf[..read].copy_from_slice(&self.bytes[..read]);
+            self.bytes = &self.bytes[read..];
+            Ok(read)
+        }
+    }
+
+    #[test]
+    fn stream_matches_scanner() {
+        let expected = Scanner::new(SOURCE).scan().unwrap();
+
+        for chunk_size in [1, 2, 3, 5, 7, 64] {
+            let stream = StreamScanner::with_chunk_size(Cursor::new(SOURCE), chunk_size);
+            assert_eq!(
+                expected,
+                stream.scan().unwrap(),
+                "chunk size {}",
+                chunk_size
+            );
+        }
+
+        for step in [1, 3] {
+            let reader = Trickle {
+                bytes: SOURCE.as_bytes(),
+                step,
+            };
+            assert_eq!(expected, StreamScanner::new(reader).scan().unwrap());
+        }
+    }
+
+    #[test]
+    fn stream_column_units() {
+        let source = "{\n\t\"e\u{301}👨\u{200d}👩\":\t[\"🇬🇧\", \"中\"]\n}";
+
+        for column_unit in [ColumnUnit::Utf16, ColumnUnit::Display { tab_size: 4 }] {
+            let config = ScannerConfig {
+                column_unit,
+                ..Default::default()
+            };
+            let expected = Scanner::with_config(source, config).scan().unwrap();
+
+            for chunk_size in [1, 3, 64] {
+                let mut stream = StreamScanner::with_config(Cursor::new(source), config);
+                stream.chunk_size = chunk_size;
+
+                assert_eq!(expected, stream.scan().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn stream_json5() {
+        let source = "// c\n{a: 'x\\\r\ny', /* ** */ b: -0x1F, c: +Infinity, d: .5}\n/* end */";
+        let config = ScannerConfig {
+            dialect: Dialect::Json5,
+            ..Default::default()
+        };
+        let expected = Scanner::with_config(source, config).scan().unwrap();
+
+        for chunk_size in [1, 2, 3, 64] {
+            let mut stream = StreamScanner::with_config(Cursor::new(source), config);
+            stream.chunk_size = chunk_size;
+
+            assert_eq!(
+                expected,
+                stream.scan().unwrap(),
+                "chunk size {}",
+                chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn stream_trivia() {
+        let source = "// head\r\n{\"a\": [1, /* x */ 2], // tail\n\t\"b\":  null\n}\n\n";
+        let config = ScannerConfig {
+            dialect: Dialect::Jsonc,
+            trivia: true,
+            ..Default::default()
+        };
+        let expected = Scanner::with_config(source, config).scan().unwrap();
+
+        for chunk_size in [1, 2, 3, 64] {
+            let mut stream = StreamScanner::with_config(Cursor::new(source), config);
+            stream.chunk_size = chunk_size;
+
+            let tokens = stream.scan().unwrap();
+            let round_trip: String = tokens
+                .iter()
+                .map(|token| token.trivia.as_ref().unwrap().to_string())
+                .collect();
+
+            assert_eq!(expected, tokens, "chunk size {}", chunk_size);
+            assert_eq!(source, round_trip);
+        }
+    }
+
+    #[test]
+    fn stream_eof() {
+        for chunk_size in [1, 2, 64] {
+            let stream = StreamScanner::with_chunk_size(Cursor::new("[1] \n "), chunk_size);
+            let tokens = stream.scan().unwrap();
+
+            assert_eq!(4, tokens.len());
+            assert_eq!(
+                TokenPosition::new(2, 2, 2, 2, 6, 6),
+                tokens[3].token_position
+            );
+        }
+
+        let config = ScannerConfig {
+            require_value: true,
+            ..Default::default()
+        };
+
+        for chunk_size in [1, 64] {
+            let mut stream = StreamScanner::with_config(Cursor::new(" \n "), config);
+            stream.chunk_size = chunk_size;
+
+            assert_eq!(
+                Err(ScannerError::EmptyInput(TokenPosition::new(
+                    2, 2, 2, 2, 3, 3
+                ))),
+                stream.scan()
+            );
+        }
+    }
+
+    #[test]
+    fn stream_errors() {
+        let stream = StreamScanner::with_chunk_size(Cursor::new("[1,\n 01]"), 1);
+        assert_eq!(
+            Err(ScannerError::LeadingZero(
+                TokenPosition::new(2, 2, 2, 4, 5, 7),
+                "01".to_string()
+            )),
+            stream.scan()
+        );
+
+        let stream = StreamScanner::with_chunk_size(Cursor::new("[\"abc"), 2);
+        assert_eq!(
+            Err(ScannerError::UnterminatedString(TokenPosition::new(
+                1, 1, 2, 6, 1, 5
+            ))),
+            stream.scan()
+        );
+    }
+
+    #[test]
+    fn stream_recover() {
+        let source = "[tru, \"a\\qb\", \"abc,\n  01, \"\u{e9}\", 2]";
+        let config = ScannerConfig {
+            recover: true,
+            ..Default::default()
+        };
+
+        let expected = Scanner::with_config(source, config).scan_all();
+
+        for chunk_size in [1, 2, 5] {
+            let mut stream = StreamScanner::with_config(Cursor::new(source), config);
+            stream.chunk_size = chunk_size;
+
+            assert_eq!(expected, stream.scan_all());
+        }
+
+        assert_eq!(4, expected.1.len());
+    }
+
+    #[test]
+    fn stream_invalid_utf8() {
+        let stream = StreamScanner::with_chunk_size(Cursor::new(b"[1,\n \"a\xff\"]"), 2);
+        assert_eq!(
+            Err(ScannerError::InvalidUtf8(TokenPosition::new(
+                2, 2, 4, 5, 7, 8
+            ))),
+            stream.scan()
+        );
+
+        let stream = StreamScanner::new(Cursor::new(b"[\"\xf0\x9f\x98"));
+        assert_eq!(
+            Err(ScannerError::InvalidUtf8(TokenPosition::new(
+                1, 1, 3, 4, 2, 3
+            ))),
+            stream.scan()
+        );
+    }
+
+    #[test]
+    fn stream_detects_encoding() {
+        let text = "\u{feff}{\"naïve\": [\"👍\", 1]}";
+        let expected = Scanner::new(text).scan();
+
+        let utf16: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let utf32: Vec<u8> = text
+            .chars()
+            .flat_map(|char| u32::from(char).to_le_bytes())
+            .collect();
+
+        for input in [text.as_bytes().to_vec(), utf16, utf32] {
+            for chunk_size in [1, 3, 1024] {
+                let stream = StreamScanner::with_chunk_size(Cursor::new(&input), chunk_size);
+
+                assert_eq!(expected, stream.scan());
+            }
+        }
+
+        assert_eq!(1, expected.unwrap()[0].token_position.column_start);
+    }
+
+    #[test]
+    fn stream_invalid_utf16() {
+        let input: Vec<u8> = "[\"a"
+            .encode_utf16()
+            .chain([0xdc00, 0x22, 0x5d])
+            .flat_map(u16::to_le_bytes)
+            .collect();
+
+        assert_eq!(
+            Err(ScannerError::InvalidEncoding(
+                TokenPosition::new(1, 1, 4, 5, 3, 4),
+                Encoding::Utf16Le
+            )),
+            StreamScanner::with_chunk_size(Cursor::new(input), 2).scan()
+        );
+    }
+
+    #[test]
+    fn stream_read_error() {
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk on fire"))
+            }
+        }
+
+        let mut stream = StreamScanner::new(Broken);
+
+        assert_eq!(
+            Some(Err(ScannerError::Io(
+                TokenPosition::new(1, 1, 1, 2, 0, 1),
+                "disk on fire".to_string()
+            ))),
+            stream.next()
+        );
+        assert_eq!(None, stream.next());
+    }
+
+    #[test]
+    fn stream_limits() {
+        let config = ScannerConfig {
+            limits: Limits {
+                max_document_size: 32,
+                max_tokens: 4,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let long_string = format!("[\"{}\"]", "a".repeat(100));
+
+        for chunk_size in [1, 4, 64] {
+            let mut stream = StreamScanner::with_chunk_size(Cursor::new(&long_string), chunk_size);
+            stream.config = config;
+
+            let (tokens, errors) = stream.scan_all();
+
+            assert_eq!(1, tokens.len());
+            assert_eq!(
+                vec![ScannerError::DocumentTooLarge(
+                    TokenPosition::new(1, 1, 2, 34, 1, 33),
+                    32
+                )],
+                errors
+            );
+
+            let mut stream = StreamScanner::with_chunk_size(Cursor::new("[1, 2]"), chunk_size);
+            stream.config = config;
+
+            assert_eq!(Scanner::with_config("[1, 2]", config).scan(), stream.scan());
+        }
+
+        let config = ScannerConfig {
+            limits: Limits {
+                max_string_length: 16,
+                max_number_length: 8,
+                max_document_size: 64 * 1024 * 1024,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let endless = |prefix: &'static str, byte: u8| {
+            let mut stream = StreamScanner::new(Cursor::new(prefix).chain(std::io::repeat(byte)));
+            stream.config = config;
+            stream.scan()
+        };
+
+        assert_eq!(
+            Err(ScannerError::StringTooLong(
+                TokenPosition::new(1, 1, 2, 20, 1, 19),
+                16
+            )),
+            endless("[\"", b'a')
+        );
+        assert_eq!(
+            Err(ScannerError::NumberTooLong(
+                TokenPosition::new(1, 1, 2, 8194, 1, 8193),
+                8
+            )),
+            endless("[", b'1')
+        );
+
+        let mut reader = std::io::repeat(b' ').take(1024 * 1024);
+        let mut stream = StreamScanner::with_chunk_size(&mut reader, 10);
+        stream.config.limits.max_document_size = 32;
+
+        assert_eq!(
+            Err(ScannerError::DocumentTooLarge(
+                TokenPosition::new(1, 1, 34, 34, 33, 33),
+                32
+            )),
+            stream.scan()
+        );
+        assert_eq!(1024 * 1024 - 33, reader.limit());
+    }
+}
@@ -0,0 +1,752 @@
+///Size of the blocks the source is classified in, one bit per byte of a `u64` mask
+const BLOCK: usize = 64;
+
+const EVEN_BITS: u64 = 0x5555_5555_5555_5555;
+
+///Implementation used to classify the bytes of a block
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Backend {
+    ///One byte at a time, available everywhere
+    Scalar,
+    ///16 bytes at a time on x86_64
+    Sse2,
+    ///32 bytes at a time on x86_64
+    Avx2,
+    ///16 bytes at a time on aarch64
+    Neon,
+}
+
+impl Backend {
+    ///Fastest backend the running CPU supports
+    pub fn detect() -> Self {
+        [Self::Avx2, Self::Sse2, Self::Neon]
+            .into_iter()
+            .find(Self::is_supported)
+            .unwrap_or(Self::Scalar)
+    }
+
+    ///Whether the running CPU can use this backend
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Self::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Self::Sse2 => std::arch::is_x86_feature_detected!("sse2"),
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx2 => std::arch::is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "aarch64")]
+            Self::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            _ => false,
+        }
+    }
+
+    fn classify(&self, block: &[u8; BLOCK]) -> Classes {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            //SAFETY: only selected when the CPU supports it, see `StructuralIndex::with_backend`
+            Self::Sse2 => unsafe { x86::classify_sse2(block) },
+            #[cfg(target_arch = "x86_64")]
+            //SAFETY: as above
+            Self::Avx2 => unsafe { x86::classify_avx2(block) },
+            #[cfg(target_arch = "aarch64")]
+            //SAFETY: as above
+            Self::Neon => unsafe { arm::classify_neon(block) },
+            _ => classify_scalar(block),
+        }
+    }
+}
+
+///Offsets of the tokens in a document, found ahead of scanning
+///
+///This is the first stage of simdjson: the source is classified 64 bytes at a time into bit masks
+///of quotes, backslashes, structural characters and whitespace, using SIMD instructions when the
+///CPU has them. Bit arithmetic on the masks then finds which quotes are escaped, which bytes are
+///inside strings and where every token starts, without branching on each byte.
+///
+///The index holds the offset of every structural character (`{}[]:,`) outside a string, the
+///first byte of every other token and the closing quote of every string. Strings without a
+///backslash or control character in them are marked as plain. A
+///[`Scanner`](super::scanner::Scanner) built with [`Scanner::indexed`] uses it to jump over
+///whitespace, and to take plain strings, numbers and literals in one step from one offset to the
+///next.
+///
+///The index is only a hint. It follows the JSON grammar without validating it, so in input with
+///errors or from another dialect an offset may not be a token boundary, and the scanner checks
+///every jump before taking it.
+///
+///[`Scanner::indexed`]: super::scanner::Scanner::indexed
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::structural_index::StructuralIndex;
+///let index = StructuralIndex::new(br#"{"a": [1, "x\"y"]}"#);
+///
+///assert_eq!(&[0, 1, 3, 4, 6, 7, 8, 10, 15, 16, 17], index.positions());
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub struct StructuralIndex {
+    positions: Vec<usize>,
+    ///One bit per position, set for the opening quote of a plain string
+    plain: Vec<u64>,
+    ///Position of the last opening quote, which strings continuing into the next block belong to
+    last_opening: usize,
+}
+
+impl StructuralIndex {
+    ///Index `source` with the fastest backend the CPU supports
+    pub fn new(source: &[u8]) -> Self {
+        Self::with_backend(source, Backend::detect())
+    }
+
+    ///Index `source` with a given backend, falling back to [`Backend::Scalar`] when the CPU does
+    ///not support it
+    pub fn with_backend(source: &[u8], backend: Backend) -> Self {
+        let backend = match backend.is_supported() {
+            true => backend,
+            false => Backend::Scalar,
+        };
+
+        let mut index = Self {
+            positions: Vec::with_capacity(source.len() / 8),
+            plain: Vec::with_capacity(source.len() / 512 + 1),
+            last_opening: 0,
+        };
+        let mut state = State::default();
+
+        let mut blocks = source.chunks_exact(BLOCK);
+
+        for (number, block) in blocks.by_ref().enumerate() {
+            let classes = backend.classify(block.try_into().unwrap());
+            index.push(number * BLOCK, state.next(classes));
+        }
+
+        let remainder = blocks.remainder();
+
+        if !remainder.is_empty() {
+            let mut block = [b' '; BLOCK];
+            block[..remainder.len()].copy_from_slice(remainder);
+
+            let classes = backend.classify(&block);
+            let offset = source.len() - remainder.len();
+            index.push(offset, state.next(classes));
+        }
+
+        index
+    }
+
+    ///Offsets in increasing order
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    ///Whether the offset at `index` in [`StructuralIndex::positions`] opens a string without
+    ///escapes or control characters, which then ends at the next offset
+    pub fn is_plain_string(&self, index: usize) -> bool {
+        self.plain
+            .get(index / 64)
+            .is_some_and(|bits| bits & (1 << (index % 64)) != 0)
+    }
+
+    ///Add the positions found in the block at `offset`, marking the strings opened in it as
+    ///plain until an escape or control character shows they are not
+    fn push(&mut self, offset: usize, found: Found) {
+        let first = self.positions.len();
+        let mut bits = found.positions;
+
+        self.positions
+            .extend((0..found.positions.count_ones()).map(|_| {
+                let position = offset + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                position
+            }));
+
+        self.plain.resize(self.positions.len().div_ceil(64), 0);
+
+        //Position of the bit at `bit` in the block, counting the positions before it
+        let position =
+            |bit: u32| first + (found.positions & ((1 << bit) - 1)).count_ones() as usize;
+
+        let mut openings = found.openings;
+
+        while openings != 0 {
+            let index = position(openings.trailing_zeros());
+            self.plain[index / 64] |= 1 << (index % 64);
+            openings &= openings - 1;
+        }
+
+        let mut escapes = found.escapes;
+
+        while escapes != 0 {
+            let bit = escapes.trailing_zeros();
+            let openings_before = found.openings & (u64::MAX >> (63 - bit));
+
+            let index = match openings_before {
+                0 => self.last_opening,
+                _ => position(63 - openings_before.leading_zeros()),
+            };
+            self.plain[index / 64] &= !(1 << (index % 64));
+
+            escapes &= escapes - 1;
+        }
+
+        if found.openings != 0 {
+            self.last_opening = position(63 - found.openings.leading_zeros());
+        }
+    }
+
+    ///Index into `positions` of the first offset at or after `offset`
+    ///
+    ///`hint` is where the last search ended, which makes searching forward from it cheap.
+    pub(crate) fn search(&self, offset: usize, hint: usize) -> usize {
+        let positions = &self.positions;
+        let hint = hint.min(positions.len());
+
+        if hint > 0 && positions[hint - 1] >= offset {
+            return positions.partition_point(|position| *position < offset);
+        }
+
+        let ahead = &positions[hint..];
+
+        match ahead
+            .iter()
+            .take(8)
+            .position(|position| *position >= offset)
+        {
+            Some(index) => hint + index,
+            None => hint + ahead.partition_point(|position| *position < offset),
+        }
+    }
+}
+
+///Bit masks of the bytes in a block that are of interest
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct Classes {
+    backslash: u64,
+    quote: u64,
+    op: u64,
+    whitespace: u64,
+    ///Bytes below `0x20`, which a JSON string cannot contain unescaped
+    control: u64,
+}
+
+///What the masks of a block show
+#[derive(Debug, Clone, Copy)]
+struct Found {
+    ///Token offsets, one bit per byte
+    positions: u64,
+    ///Quotes opening a string
+    openings: u64,
+    ///Backslashes and control characters inside strings
+    escapes: u64,
+}
+
+///What carries over from one block to the next
+#[derive(Debug, Default)]
+struct State {
+    ///The first byte of the next block is escaped by a backslash at the end of this one
+    escaped: u64,
+    ///All ones while inside a string at the end of the block
+    in_string: u64,
+    ///The last byte of the block is part of a scalar
+    scalar: u64,
+}
+
+impl State {
+    ///Find the positions in a block from its classes
+    fn next(&mut self, classes: Classes) -> Found {
+        let escaped = self.escaped(classes.backslash);
+        let quote = classes.quote & !escaped;
+
+        //Each unescaped quote toggles whether the bytes after it are in a string. The mask includes
+        //the opening quote but not the closing one
+        let in_string = prefix_xor(quote) ^ self.in_string;
+        self.in_string = ((in_string as i64) >> 63) as u64;
+
+        let scalar = !(classes.op | classes.whitespace | quote | in_string);
+        let scalar_start = scalar & !((scalar << 1) | self.scalar);
+        self.scalar = scalar >> 63;
+
+        Found {
+            positions: (classes.op & !in_string) | scalar_start | quote,
+            openings: quote & in_string,
+            escapes: (classes.backslash | classes.control) & in_string,
+        }
+    }
+
+    ///Mask of the bytes escaped by a backslash, which follow an odd number of backslashes
+    fn escaped(&mut self, backslash: u64) -> u64 {
+        let backslash = backslash & !self.escaped;
+        let follows_escape = (backslash << 1) | self.escaped;
+
+        //Runs of backslashes starting on an odd bit. Adding the backslashes to their starts carries
+        //through each run, so the bit after the run lands on an even bit for an odd length run
+        let odd_starts = backslash & !EVEN_BITS & !follows_escape;
+        let (sequences_on_even_bits, overflow) = odd_starts.overflowing_add(backslash);
+        self.escaped = u64::from(overflow);
+
+        let invert_mask = sequences_on_even_bits << 1;
+
+        (EVEN_BITS ^ invert_mask) & follows_escape
+    }
+}
+
+///Each bit becomes the xor of itself and every bit below it
+fn prefix_xor(mut bits: u64) -> u64 {
+    for shift in [1, 2, 4, 8, 16, 32] {
+        bits ^= bits << shift;
+    }
+
+    bits
+}
+
+fn classify_scalar(block: &[u8; BLOCK]) -> Classes {
+    let mut classes = Classes::default();
+
+    for (index, byte) in block.iter().enumerate() {
+        let bit = 1 << index;
+
+        match byte {
+            b'\\' => classes.backslash |= bit,
+            b'"' => classes.quote |= bit,
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => classes.op |= bit,
+            b' ' | b'\t' | b'\n' | b'\r' => classes.whitespace |= bit,
+            _ => {}
+        }
+
+        if *byte < 0x20 {
+            classes.control |= bit;
+        }
+    }
+
+    classes
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    use super::{Classes, BLOCK};
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn classify_sse2(block: &[u8; BLOCK]) -> Classes {
+        let mut classes = Classes::default();
+
+        for lane in 0..BLOCK / 16 {
+            let chunk = _mm_loadu_si128(block.as_ptr().add(lane * 16) as *const __m128i);
+            let eq = |byte: u8| _mm_cmpeq_epi8(chunk, _mm_set1_epi8(byte as i8));
+            let mask = |bytes: __m128i| (_mm_movemask_epi8(bytes) as u16 as u64) << (lane * 16);
+
+            let op = _mm_or_si128(
+                _mm_or_si128(
+                    _mm_or_si128(eq(b'{'), eq(b'}')),
+                    _mm_or_si128(eq(b'['), eq(b']')),
+                ),
+                _mm_or_si128(eq(b':'), eq(b',')),
+            );
+            let whitespace = _mm_or_si128(
+                _mm_or_si128(eq(b' '), eq(b'\t')),
+                _mm_or_si128(eq(b'\n'), eq(b'\r')),
+            );
+
+            classes.backslash |= mask(eq(b'\\'));
+            classes.quote |= mask(eq(b'"'));
+            classes.op |= mask(op);
+            classes.whitespace |= mask(whitespace);
+            classes.control |= mask(_mm_cmpeq_epi8(
+                _mm_min_epu8(chunk, _mm_set1_epi8(0x1f)),
+                chunk,
+            ));
+        }
+
+        classes
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn classify_avx2(block: &[u8; BLOCK]) -> Classes {
+        let mut classes = Classes::default();
+
+        for lane in 0..BLOCK / 32 {
+            let chunk = _mm256_loadu_si256(block.as_ptr().add(lane * 32) as *const __m256i);
+            let eq = |byte: u8| _mm256_cmpeq_epi8(chunk, _mm256_set1_epi8(byte as i8));
+            let mask = |bytes: __m256i| (_mm256_movemask_epi8(bytes) as u32 as u64) << (lane * 32);
+
+            let op = _mm256_or_si256(
+                _mm256_or_si256(
+                    _mm256_or_si256(eq(b'{'), eq(b'}')),
+                    _mm256_or_si256(eq(b'['), eq(b']')),
+                ),
+                _mm256_or_si256(eq(b':'), eq(b',')),
+            );
+            let whitespace = _mm256_or_si256(
+                _mm256_or_si256(eq(b' '), eq(b'\t')),
+                _mm256_or_si256(eq(b'\n'), eq(b'\r')),
+            );
+
+            classes.backslash |= mask(eq(b'\\'));
+            classes.quote |= mask(eq(b'"'));
+            classes.op |= mask(op);
+            classes.whitespace |= mask(whitespace);
+            classes.control |= mask(_mm256_cmpeq_epi8(
+                _mm256_min_epu8(chunk, _mm256_set1_epi8(0x1f)),
+                chunk,
+            ));
+        }
+
+        classes
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arm {
+    use std::arch::aarch64::*;
+
+    use super::{Classes, BLOCK};
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn classify_neon(block: &[u8; BLOCK]) -> Classes {
+        let mut classes = Classes::default();
+
+        for lane in 0..BLOCK / 16 {
+            let chunk = vld1q_u8(block.as_ptr().add(lane * 16));
+            let eq = |byte: u8| vceqq_u8(chunk, vdupq_n_u8(byte));
+            let mask = |bytes: uint8x16_t| u64::from(movemask(bytes)) << (lane * 16);
+
+            let op = vorrq_u8(
+                vorrq_u8(vorrq_u8(eq(b'{'), eq(b'}')), vorrq_u8(eq(b'['), eq(b']'))),
+                vorrq_u8(eq(b':'), eq(b',')),
+            );
+            let whitespace = vorrq_u8(
+                vorrq_u8(eq(b' '), eq(b'\t')),
+                vorrq_u8(eq(b'\n'), eq(b'\r')),
+            );
+
+            classes.backslash |= mask(eq(b'\\'));
+            classes.quote |= mask(eq(b'"'));
+            classes.op |= mask(op);
+            classes.whitespace |= mask(whitespace);
+            classes.control |= mask(vcltq_u8(chunk, vdupq_n_u8(0x20)));
+        }
+
+        classes
+    }
+
+    ///One bit per byte of a comparison result, which NEON has no single instruction for
+    #[target_feature(enable = "neon")]
+    unsafe fn movemask(bytes: uint8x16_t) -> u16 {
+        const WEIGHTS: [u8; 16] = [1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+
+        let bits = vandq_u8(bytes, vld1q_u8(WEIGHTS.as_ptr()));
+
+        u16::from(vaddv_u8(vget_low_u8(bits))) | (u16::from(vaddv_u8(vget_high_u8(bits))) << 8)
+    }
+}
+
+#[cfg(test)]
+mod structural_index_tests {
+    use crate::{
+        scanner::{
+            dialect::Dialect, limits::Limits, scanner::Scanner, scanner_config::ScannerConfig,
+        },
+        source_map::column_unit::ColumnUnit,
+    };
+
+    use super::{Backend, StructuralIndex};
+
+    const BACKENDS: [Backend; 4] = [Backend::Scalar, Backend::Sse2, Backend::Avx2, Backend::Neon];
+
+    ///Small xorshift generator so the differential tests are repeatable
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 as usize
+        }
+
+        fn text(&mut self, pieces: &[&str], max_len: usize) -> String {
+            let len = self.next() % max_len;
+
+            (0..len)
+                .map(|_| pieces[self.next() % pieces.len()])
+                .collect()
+        }
+    }
+
+    ///The index one byte at a time, tracking escapes and strings as the masks do
+    fn reference(source: &[u8]) -> Vec<usize> {
+        let mut positions = vec![];
+        let (mut escaped, mut in_string, mut previous_scalar) = (false, false, false);
+
+        for (index, byte) in source.iter().enumerate() {
+            let quote = *byte == b'"' && !escaped;
+            let inside = in_string;
+            let op = matches!(byte, b'{' | b'}' | b'[' | b']' | b':' | b',');
+            let whitespace = matches!(byte, b' ' | b'\t' | b'\n' | b'\r');
+            let scalar = !quote && !inside && !op && !whitespace;
+
+            if quote || (op && !inside) || (scalar && !previous_scalar) {
+                positions.push(index);
+            }
+
+            escaped = *byte == b'\\' && !escaped;
+            in_string ^= quote;
+            previous_scalar = scalar;
+        }
+
+        positions
+    }
+
+    #[test]
+    fn backends_match_reference() {
+        let pieces = [
+            "{", "}", "[", "]", ":", ",", "\"", "\\", "\\\\", "\\\"", " ", "\n", "\t", "a", "1",
+            "-", "é", "👍", "true",
+        ];
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..2000 {
+            let source = random.text(&pieces, 200);
+            let expected = reference(source.as_bytes());
+
+            for backend in BACKENDS {
+                assert_eq!(
+                    expected,
+                    StructuralIndex::with_backend(source.as_bytes(), backend).positions(),
+                    "{:?} {:?}",
+                    backend,
+                    source
+                );
+            }
+        }
+    }
+
+    ///Whether each string opened by the reference index holds no backslash or control byte
+    fn reference_plain(source: &[u8]) -> Vec<bool> {
+        let positions = reference(source);
+        let mut plain = vec![false; positions.len()];
+        let mut opening = None;
+
+        for (slot, position) in positions.iter().enumerate() {
+            if source[*position] != b'"' {
+                continue;
+            }
+
+            match opening.take() {
+                Some(open) => {
+                    plain[open] = source[positions[open] + 1..*position]
+                        .iter()
+                        .all(|byte| *byte != b'\\' && *byte >= 0x20)
+                }
+                None => opening = Some(slot),
+            }
+        }
+
+        if let Some(open) = opening {
+            plain[open] = source[positions[open] + 1..]
+                .iter()
+                .all(|byte| *byte != b'\\' && *byte >= 0x20);
+        }
+
+        plain
+    }
+
+    #[test]
+    fn plain_strings_match_reference() {
+        let pieces = [
+            "{", "}", "[", "]", ":", ",", "\"", "\\", "\\\"", " ", "\n", "\u{1}", "\u{1f}", "a",
+            "1", "é", "👍",
+        ];
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..2000 {
+            let source = random.text(&pieces, 200);
+            let expected = reference_plain(source.as_bytes());
+
+            for backend in BACKENDS {
+                let index = StructuralIndex::with_backend(source.as_bytes(), backend);
+                let plain: Vec<bool> = (0..index.positions().len())
+                    .map(|slot| index.is_plain_string(slot))
+                    .collect();
+
+                assert_eq!(expected, plain, "{:?} {:?}", backend, source);
+            }
+        }
+    }
+
+    #[test]
+    fn escapes_across_blocks() {
+        for backslashes in 0..5 {
+            for start in 55..70 {
+                let mut source = vec![b' '; start];
+                source[0] = b'"';
+                source.extend(std::iter::repeat_n(b'\\', backslashes));
+                source.extend_from_slice(b"\" , 1 \" x");
+
+                for backend in BACKENDS {
+                    assert_eq!(
+                        reference(&source),
+                        StructuralIndex::with_backend(&source, backend).positions()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn detect_backend() {
+        assert!(Backend::detect().is_supported());
+        assert!(Backend::Scalar.is_supported());
+
+        #[cfg(target_arch = "x86_64")]
+        assert!(Backend::Sse2.is_supported());
+        #[cfg(target_arch = "x86_64")]
+        assert!(!Backend::Neon.is_supported());
+    }
+
+    #[test]
+    fn indexed_scanner_matches_scanner() {
+        let pieces = [
+            "{",
+            "}",
+            "[",
+            "]",
+            ":",
+            ",",
+            " ",
+            "  ",
+            "          ",
+            "\n",
+            "\r\n",
+            "\t",
+            "\"abc\"",
+            "\"abcdefgh\"",
+            "\"é👍\"",
+            "\"a\\\"b\"",
+            "\"\\u00e9\"",
+            "\"tab\there\"",
+            "\"a\nb\"",
+            "\"",
+            "\\",
+            "12",
+            "-0.5e3",
+            "01",
+            "1.",
+            "-",
+            "0x1f",
+            "true",
+            "nul",
+            "'x'",
+            "// c\n",
+            "/* c */",
+            "@",
+            "\u{feff}",
+            "Infinity",
+        ];
+        let configs = [
+            ScannerConfig::default(),
+            ScannerConfig {
+                recover: true,
+                ..Default::default()
+            },
+            ScannerConfig {
+                recover: true,
+                dialect: Dialect::Json5,
+                ..Default::default()
+            },
+            ScannerConfig {
+                dialect: Dialect::Jsonc,
+                trivia: true,
+                ..Default::default()
+            },
+            ScannerConfig {
+                recover: true,
+                column_unit: ColumnUnit::Utf8,
+                ..Default::default()
+            },
+            ScannerConfig {
+                recover: true,
+                column_unit: ColumnUnit::Display { tab_size: 4 },
+                ..Default::default()
+            },
+            ScannerConfig {
+                recover: true,
+                dialect: Dialect::Json5,
+                column_unit: ColumnUnit::Utf16,
+                ..Default::default()
+            },
+            ScannerConfig {
+                recover: true,
+                limits: Limits {
+                    max_string_length: 4,
+                    max_number_length: 3,
+                    max_literal_length: 3,
+                    max_document_size: 40,
+                    max_tokens: 12,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..1000 {
+            let source = random.text(&pieces, 60);
+
+            for config in configs {
+                assert_eq!(
+                    Scanner::with_config(&source, config).scan_all(),
+                    Scanner::indexed(&source, config).scan_all(),
+                    "{:?} {:?}",
+                    config,
+                    source
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_scanner_reports_limits() {
+        let string = ScannerConfig {
+            limits: Limits {
+                max_string_length: 4,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let document = ScannerConfig {
+            limits: Limits {
+                max_document_size: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let spaces = " ".repeat(30);
+
+        for (source, config) in [
+            (r#"["abcdefgh"]"#.to_string(), string),
+            (format!("[1]{}", spaces), document),
+            (format!("[1,{}2]", spaces), document),
+        ] {
+            assert_eq!(
+                Scanner::with_config(&source, config).scan_all(),
+                Scanner::indexed(&source, config).scan_all(),
+                "{:?}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn indexed_scanner_on_large_document() {
+        let record =
+            "{\"id\": 1, \"name\": \"café \\\"bar\\\"\",\n\t\"tags\": [\"a\", \"b\"], \"n\": null}";
+        let source = format!("[{}]", vec![record; 200].join(",\r\n  "));
+
+        assert_eq!(
+            Scanner::new(&source).scan(),
+            Scanner::indexed(&source, ScannerConfig::default()).scan()
+        );
+    }
+}
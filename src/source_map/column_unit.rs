@@ -0,0 +1,155 @@
+use super::unicode;
+
+///The unit columns are counted in
+///
+///Tools disagree on what a column is: LSP clients count UTF-16 code units, terminals count
+///display cells, and byte offsets are what most file APIs want. Every unit starts counting at
+///column 1.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ColumnUnit {
+    ///UTF-8 bytes
+    Utf8,
+    ///Unicode scalar values, i.e. Rust `char`s
+    #[default]
+    Scalar,
+    ///UTF-16 code units, as used by the Language Server Protocol
+    Utf16,
+    ///Terminal cells, where wide characters and emoji take two cells and a tab moves to the next
+    ///multiple of `tab_size`
+    ///
+    ///This is an approximation for the scripts and emoji editors most often meet, from the small
+    ///tables in `source_map::unicode` rather than the full Unicode property data. Combining marks,
+    ///variation selectors, emoji modifiers, ZWJ sequences and flags are joined to the character
+    ///before them, but spacing marks of Indic scripts count as cells of their own and conjoining
+    ///Hangul jamo count one or two cells each instead of forming a syllable.
+    Display { tab_size: usize },
+}
+
+///Advance a column one character at a time
+///
+///Display columns depend on the characters before them, e.g. a combining accent adds nothing to
+///the column of the letter it follows, so the counter remembers enough of the current cluster of
+///characters to measure the next one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ColumnCounter {
+    previous: Option<char>,
+    regional_indicators: usize,
+    cluster_width: usize,
+}
+
+impl ColumnCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Forget the current cluster e.g. at the start of a new line
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    ///Column in `unit` after `char` when it is found at `column`
+    pub fn advance(&mut self, unit: ColumnUnit, column: usize, char: char) -> usize {
+        let next = match unit {
+            ColumnUnit::Utf8 => column + char.len_utf8(),
+            ColumnUnit::Scalar => column + 1,
+            ColumnUnit::Utf16 => column + char.len_utf16(),
+            ColumnUnit::Display { tab_size } => {
+                if char == '\t' {
+                    let tab_size = tab_size.max(1);
+                    ((column - 1) / tab_size + 1) * tab_size + 1
+                } else if self.extends_cluster(char) {
+                    if char == '\u{fe0f}' && self.cluster_width == 1 {
+                        self.cluster_width = 2;
+                        column + 1
+                    } else {
+                        column
+                    }
+                } else {
+                    self.cluster_width = unicode::width(char);
+                    column + self.cluster_width
+                }
+            }
+        };
+
+        if unicode::is_regional_indicator(char) {
+            self.regional_indicators += 1;
+        } else if !unicode::is_extend(char) {
+            self.regional_indicators = 0;
+        }
+
+        self.previous = Some(char);
+
+        next
+    }
+
+    fn extends_cluster(&self, char: char) -> bool {
+        let Some(previous) = self.previous else {
+            return false;
+        };
+
+        (previous == '\r' && char == '\n')
+            || (unicode::is_extend(char) && previous != '\n' && !previous.is_control())
+            || unicode::is_zero_width_joiner(previous)
+            || (unicode::is_regional_indicator(char)
+                && unicode::is_regional_indicator(previous)
+                && self.regional_indicators % 2 == 1)
+    }
+}
+
+///Measure the column reached after `text` when it starts at column 1
+pub fn column_after(text: &str, unit: ColumnUnit) -> usize {
+    let mut counter = ColumnCounter::new();
+
+    text.chars()
+        .fold(1, |column, char| counter.advance(unit, column, char))
+}
+
+#[cfg(test)]
+mod column_unit_tests {
+    use super::{column_after, ColumnUnit};
+
+    const DISPLAY: ColumnUnit = ColumnUnit::Display { tab_size: 4 };
+
+    #[test]
+    fn ascii_is_the_same_in_every_unit() {
+        for unit in [
+            ColumnUnit::Utf8,
+            ColumnUnit::Scalar,
+            ColumnUnit::Utf16,
+            DISPLAY,
+        ] {
+            assert_eq!(6, column_after("hello", unit));
+        }
+    }
+
+    #[test]
+    fn count_columns() {
+        let cases = [
+            ("é", [3, 2, 2, 2]),
+            ("e\u{301}", [4, 3, 3, 2]),
+            ("中文", [7, 3, 3, 5]),
+            ("😀", [5, 2, 3, 3]),
+            ("👍🏽", [9, 3, 5, 3]),
+            ("👨\u{200d}👩\u{200d}👧", [19, 6, 9, 3]),
+            ("🇬🇧🇫🇷", [17, 5, 9, 5]),
+            ("❤\u{fe0f}", [7, 3, 3, 3]),
+        ];
+
+        for (text, [utf8, scalar, utf16, display]) in cases {
+            assert_eq!(utf8, column_after(text, ColumnUnit::Utf8), "{}", text);
+            assert_eq!(scalar, column_after(text, ColumnUnit::Scalar), "{}", text);
+            assert_eq!(utf16, column_after(text, ColumnUnit::Utf16), "{}", text);
+            assert_eq!(display, column_after(text, DISPLAY), "{}", text);
+        }
+    }
+
+    #[test]
+    fn tab_stops() {
+        assert_eq!(5, column_after("\t", DISPLAY));
+        assert_eq!(5, column_after("ab\t", DISPLAY));
+        assert_eq!(9, column_after("abcd\t", DISPLAY));
+        assert_eq!(9, column_after("\t\t", DISPLAY));
+        assert_eq!(3, column_after("\t", ColumnUnit::Display { tab_size: 2 }));
+        assert_eq!(2, column_after("\t", ColumnUnit::Scalar));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod column_unit;
+pub mod location;
+#[allow(clippy::module_inception)]
+pub mod source_map;
+pub mod unicode;
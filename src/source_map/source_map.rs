@@ -0,0 +1,333 @@
+use crate::token::token_position::TokenPosition;
+
+use super::{
+    column_unit::{ColumnCounter, ColumnUnit},
+    location::Location,
+};
+
+///Convert between byte offsets and lines and columns within a source
+///
+///The start of every line is indexed up front so that looking up the line of an offset is a
+///binary search, and only the characters between the start of that line and the offset are
+///walked to find the column.
+///
+///Columns count Unicode scalar values unless a [`ColumnUnit`] is given, which is how positions are
+///translated for editors and terminals that count columns differently. A byte order mark at the
+///start of the source takes up no column, as editors hide it.
+///
+///# Examples
+///
+///```
+///# use jtool::source_map::{column_unit::ColumnUnit, location::Location, source_map::SourceMap};
+///let source_map = SourceMap::new("{\n  \"café\": true\n}");
+///
+///assert_eq!(Some(Location::new(2, 11, 13)), source_map.location(13));
+///assert_eq!(Some(13), source_map.offset(2, 11));
+///assert_eq!(Some("  \"café\": true"), source_map.line(2));
+///assert_eq!(Some(Location::new(2, 12, 13)), source_map.location_in(13, ColumnUnit::Utf8));
+///```
+#[derive(Debug)]
+pub struct SourceMap<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    ///Byte offset of the first character on `line`
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line.checked_sub(1)?).copied()
+    }
+
+    ///Text of `line` without its line ending
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let start = self.line_start(line)?;
+        let end = self
+            .line_start(line + 1)
+            .map_or(self.source.len(), |next| next - 1);
+
+        let text = &self.source[start..end];
+
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    ///Find the line and column of a byte offset
+    ///
+    ///The offset may be one past the last byte, which is the location of the end of input, but
+    ///must otherwise fall on a character boundary.
+    pub fn location(&self, offset: usize) -> Option<Location> {
+        self.location_in(offset, ColumnUnit::Scalar)
+    }
+
+    ///Find the line and column of a byte offset with the column counted in `unit`
+    pub fn location_in(&self, offset: usize, unit: ColumnUnit) -> Option<Location> {
+        if !self.source.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let start = self.first_column(line)?.min(offset);
+
+        let mut counter = ColumnCounter::new();
+        let column = self.source[start..offset]
+            .chars()
+            .fold(1, |column, char| counter.advance(unit, column, char));
+
+        Some(Location::new(line, column, offset))
+    }
+
+    ///Find the byte offset of a line and column
+    ///
+    ///The column may be one past the last character on the line, which is where the line ending
+    ///starts.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        self.offset_in(line, column, ColumnUnit::Scalar)
+    }
+
+    ///Find the byte offset of a line and a column counted in `unit`
+    ///
+    ///Columns that fall inside a character, such as the second half of a UTF-16 surrogate pair or
+    ///of a wide character on screen, have no offset.
+    pub fn offset_in(&self, line: usize, column: usize, unit: ColumnUnit) -> Option<usize> {
+        let start = self.first_column(line)?;
+        let text = &self.line(line)?[start - self.line_start(line)?..];
+
+        let mut counter = ColumnCounter::new();
+        let mut current = 1;
+
+        for (index, char) in text.char_indices() {
+            if current >= column {
+                return (current == column).then_some(start + index);
+            }
+
+            current = counter.advance(unit, current, char);
+        }
+
+        (current == column).then_some(start + text.len())
+    }
+
+    ///Build the position covering the bytes `offset_start..offset_end`
+    pub fn position(&self, offset_start: usize, offset_end: usize) -> Option<TokenPosition> {
+        self.position_in(offset_start, offset_end, ColumnUnit::Scalar)
+    }
+
+    ///Build the position covering the bytes `offset_start..offset_end` with columns counted in
+    ///`unit`
+    ///
+    ///Only the offsets of a scanned position are needed to convert it to another unit.
+    pub fn position_in(
+        &self,
+        offset_start: usize,
+        offset_end: usize,
+        unit: ColumnUnit,
+    ) -> Option<TokenPosition> {
+        if offset_start > offset_end {
+            return None;
+        }
+
+        let start = self.location_in(offset_start, unit)?;
+        let end = self.location_in(offset_end, unit)?;
+
+        Some(TokenPosition::new(
+            start.line,
+            end.line,
+            start.column,
+            end.column,
+            start.offset,
+            end.offset,
+        ))
+    }
+
+    ///Byte offset of column 1 on `line`, which is after the byte order mark on the first line
+    fn first_column(&self, line: usize) -> Option<usize> {
+        let start = self.line_start(line)?;
+
+        match start == 0 && self.source.starts_with('\u{feff}') {
+            true => Some('\u{feff}'.len_utf8()),
+            false => Some(start),
+        }
+    }
+
+    ///Slice of the source covered by a position
+    pub fn text(&self, position: &TokenPosition) -> Option<&'a str> {
+        self.source.get(position.offset_start..position.offset_end)
+    }
+}
+
+#[cfg(test)]
+mod source_map_tests {
+    use crate::{
+        scanner::{scanner::Scanner, scanner_config::ScannerConfig},
+        source_map::{column_unit::ColumnUnit, location::Location},
+        token::token_position::TokenPosition,
+    };
+
+    use super::SourceMap;
+
+    const SOURCE: &str = "{\r\n  \"naïve\": [1,\n 2]\n}";
+
+    #[test]
+    fn lines() {
+        let source_map = SourceMap::new(SOURCE);
+
+        assert_eq!(4, source_map.line_count());
+        assert_eq!(Some("{"), source_map.line(1));
+        assert_eq!(Some("  \"naïve\": [1,"), source_map.line(2));
+        assert_eq!(Some("}"), source_map.line(4));
+        assert_eq!(None, source_map.line(0));
+        assert_eq!(None, source_map.line(5));
+        assert_eq!(Some(3), source_map.line_start(2));
+    }
+
+    #[test]
+    fn offset_to_location() {
+        let source_map = SourceMap::new(SOURCE);
+
+        assert_eq!(Some(Location::new(1, 1, 0)), source_map.location(0));
+        assert_eq!(Some(Location::new(1, 2, 1)), source_map.location(1));
+        assert_eq!(Some(Location::new(2, 8, 11)), source_map.location(11));
+        assert_eq!(None, source_map.location(9));
+        assert_eq!(Some(Location::new(4, 2, 24)), source_map.location(24));
+        assert_eq!(None, source_map.location(25));
+    }
+
+    #[test]
+    fn location_to_offset() {
+        let source_map = SourceMap::new(SOURCE);
+
+        assert_eq!(Some(0), source_map.offset(1, 1));
+        assert_eq!(Some(1), source_map.offset(1, 2));
+        assert_eq!(Some(11), source_map.offset(2, 8));
+        assert_eq!(Some(18), source_map.offset(2, 15));
+        assert_eq!(None, source_map.offset(2, 16));
+        assert_eq!(None, source_map.offset(2, 0));
+        assert_eq!(Some(24), source_map.offset(4, 2));
+    }
+
+    #[test]
+    fn round_trip_token_positions() {
+        let source_map = SourceMap::new(SOURCE);
+
+        for token in Scanner::new(SOURCE).scan().unwrap() {
+            let position = token.token_position;
+
+            assert_eq!(
+                Some(position),
+                source_map.position(position.offset_start, position.offset_end)
+            );
+            assert_eq!(
+                Some(position.offset_start),
+                source_map.offset(position.line_start, position.column_start)
+            );
+        }
+
+        assert_eq!(
+            Some(TokenPosition::new(2, 3, 3, 3, 5, 21)),
+            source_map.position(5, 21)
+        );
+        assert_eq!(
+            Some("\"naïve\""),
+            source_map.text(&TokenPosition::new(2, 2, 3, 10, 5, 13))
+        );
+    }
+
+    #[test]
+    fn columns_in_other_units() {
+        let source = "{\"👍🏽\":\t\"中\"}";
+        let source_map = SourceMap::new(source);
+        let display = ColumnUnit::Display { tab_size: 4 };
+
+        let offset = source.find(':').unwrap();
+
+        let columns = [
+            (ColumnUnit::Utf8, 12),
+            (ColumnUnit::Scalar, 6),
+            (ColumnUnit::Utf16, 8),
+            (display, 6),
+        ];
+
+        for (unit, column) in columns {
+            assert_eq!(
+                Some(Location::new(1, column, offset)),
+                source_map.location_in(offset, unit)
+            );
+            assert_eq!(Some(offset), source_map.offset_in(1, column, unit));
+        }
+
+        assert_eq!(Some(13), source_map.offset_in(1, 9, display));
+        assert_eq!(Some(14), source_map.offset_in(1, 10, display));
+        assert_eq!(None, source_map.offset_in(1, 11, display));
+        assert_eq!(Some(17), source_map.offset_in(1, 12, display));
+        assert_eq!(Some(19), source_map.offset_in(1, 14, display));
+        assert_eq!(None, source_map.offset_in(1, 15, display));
+        assert_eq!(None, source_map.offset_in(1, 4, ColumnUnit::Utf16));
+    }
+
+    #[test]
+    fn scanner_columns_match_source_map() {
+        let source = "{\n\t\"e\u{301}👨\u{200d}👩\": [\"🇬🇧\", \"中文\"],\r\n  \"x\": 1\n}";
+        let source_map = SourceMap::new(source);
+
+        for unit in [
+            ColumnUnit::Utf8,
+            ColumnUnit::Scalar,
+            ColumnUnit::Utf16,
+            ColumnUnit::Display { tab_size: 8 },
+        ] {
+            let config = ScannerConfig {
+                column_unit: unit,
+                ..Default::default()
+            };
+
+            for token in Scanner::with_config(source, config).scan().unwrap() {
+                let position = token.token_position;
+
+                assert_eq!(
+                    source_map.position_in(position.offset_start, position.offset_end, unit),
+                    Some(position),
+                    "{:?}",
+                    unit
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn byte_order_mark_has_no_column() {
+        let source = "\u{feff}[1,\n2]";
+        let source_map = SourceMap::new(source);
+
+        assert_eq!(Some(Location::new(1, 1, 0)), source_map.location(0));
+        assert_eq!(Some(Location::new(1, 1, 3)), source_map.location(3));
+        assert_eq!(Some(Location::new(1, 2, 4)), source_map.location(4));
+        assert_eq!(Some(3), source_map.offset(1, 1));
+        assert_eq!(Some(8), source_map.offset(2, 2));
+
+        for token in Scanner::new(source).scan().unwrap() {
+            let position = token.token_position;
+
+            assert_eq!(
+                Some(position),
+                source_map.position(position.offset_start, position.offset_end)
+            );
+        }
+    }
+}
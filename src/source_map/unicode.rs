@@ -0,0 +1,163 @@
+//!Character classification used to measure columns
+//!
+//!These tables cover the ranges that matter for display columns in terminals rather than the full
+//!Unicode property database: combining marks and other extenders that take no cell of their own,
+//!and the East Asian wide and emoji ranges that take two cells on screen. See
+//![`ColumnUnit::Display`](super::column_unit::ColumnUnit::Display) for what they leave out.
+
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
+
+const EXTEND: &[(char, char)] = &[
+    ('\u{0300}', '\u{036f}'),
+    ('\u{0483}', '\u{0489}'),
+    ('\u{0591}', '\u{05bd}'),
+    ('\u{05bf}', '\u{05c7}'),
+    ('\u{0610}', '\u{061a}'),
+    ('\u{064b}', '\u{065f}'),
+    ('\u{0670}', '\u{0670}'),
+    ('\u{06d6}', '\u{06ed}'),
+    ('\u{0900}', '\u{0903}'),
+    ('\u{093a}', '\u{094f}'),
+    ('\u{0951}', '\u{0957}'),
+    ('\u{0962}', '\u{0963}'),
+    ('\u{0e31}', '\u{0e31}'),
+    ('\u{0e34}', '\u{0e3a}'),
+    ('\u{0e47}', '\u{0e4e}'),
+    ('\u{1ab0}', '\u{1aff}'),
+    ('\u{1dc0}', '\u{1dff}'),
+    ('\u{200c}', '\u{200d}'),
+    ('\u{20d0}', '\u{20ff}'),
+    ('\u{302a}', '\u{302f}'),
+    ('\u{3099}', '\u{309a}'),
+    ('\u{fe00}', '\u{fe0f}'),
+    ('\u{fe20}', '\u{fe2f}'),
+    ('\u{1f3fb}', '\u{1f3ff}'),
+    ('\u{e0020}', '\u{e007f}'),
+    ('\u{e0100}', '\u{e01ef}'),
+];
+
+const WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115f}'),
+    ('\u{231a}', '\u{231b}'),
+    ('\u{23e9}', '\u{23ec}'),
+    ('\u{25fd}', '\u{25fe}'),
+    ('\u{2614}', '\u{2615}'),
+    ('\u{2648}', '\u{2653}'),
+    ('\u{26a1}', '\u{26a1}'),
+    ('\u{26bd}', '\u{26be}'),
+    ('\u{26c4}', '\u{26c5}'),
+    ('\u{26d4}', '\u{26d4}'),
+    ('\u{26ea}', '\u{26ea}'),
+    ('\u{26f2}', '\u{26f5}'),
+    ('\u{26fa}', '\u{26fd}'),
+    ('\u{2705}', '\u{2705}'),
+    ('\u{270a}', '\u{270b}'),
+    ('\u{2728}', '\u{2728}'),
+    ('\u{274c}', '\u{274c}'),
+    ('\u{2753}', '\u{2755}'),
+    ('\u{2757}', '\u{2757}'),
+    ('\u{2795}', '\u{2797}'),
+    ('\u{27b0}', '\u{27b0}'),
+    ('\u{27bf}', '\u{27bf}'),
+    ('\u{2b1b}', '\u{2b1c}'),
+    ('\u{2b50}', '\u{2b50}'),
+    ('\u{2b55}', '\u{2b55}'),
+    ('\u{2e80}', '\u{303e}'),
+    ('\u{3041}', '\u{33ff}'),
+    ('\u{3400}', '\u{4dbf}'),
+    ('\u{4e00}', '\u{9fff}'),
+    ('\u{a000}', '\u{a4cf}'),
+    ('\u{a960}', '\u{a97f}'),
+    ('\u{ac00}', '\u{d7a3}'),
+    ('\u{f900}', '\u{faff}'),
+    ('\u{fe10}', '\u{fe19}'),
+    ('\u{fe30}', '\u{fe6f}'),
+    ('\u{ff00}', '\u{ff60}'),
+    ('\u{ffe0}', '\u{ffe6}'),
+    ('\u{16fe0}', '\u{16fe4}'),
+    ('\u{17000}', '\u{18cff}'),
+    ('\u{1b000}', '\u{1b2ff}'),
+    ('\u{1f004}', '\u{1f004}'),
+    ('\u{1f0cf}', '\u{1f0cf}'),
+    ('\u{1f18e}', '\u{1f18e}'),
+    ('\u{1f191}', '\u{1f19a}'),
+    ('\u{1f1e6}', '\u{1f1ff}'),
+    ('\u{1f200}', '\u{1f251}'),
+    ('\u{1f300}', '\u{1f64f}'),
+    ('\u{1f680}', '\u{1f6ff}'),
+    ('\u{1f7e0}', '\u{1f7eb}'),
+    ('\u{1f90c}', '\u{1f9ff}'),
+    ('\u{1fa70}', '\u{1faff}'),
+    ('\u{20000}', '\u{3fffd}'),
+];
+
+fn in_table(table: &[(char, char)], char: char) -> bool {
+    table
+        .binary_search_by(|(start, end)| {
+            if char < *start {
+                std::cmp::Ordering::Greater
+            } else if char > *end {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+///Whether `char` joins the character before it without taking a cell of its own
+pub fn is_extend(char: char) -> bool {
+    in_table(EXTEND, char)
+}
+
+pub fn is_zero_width_joiner(char: char) -> bool {
+    char == ZERO_WIDTH_JOINER
+}
+
+pub fn is_regional_indicator(char: char) -> bool {
+    ('\u{1f1e6}'..='\u{1f1ff}').contains(&char)
+}
+
+///Number of terminal cells `char` takes when it does not join the character before it
+pub fn width(char: char) -> usize {
+    if char.is_control() || is_extend(char) {
+        0
+    } else if in_table(WIDE, char) {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod unicode_tests {
+    use super::{is_extend, width};
+
+    #[test]
+    fn tables_are_sorted() {
+        for table in [super::EXTEND, super::WIDE] {
+            assert!(table.windows(2).all(|pair| pair[0].1 < pair[1].0));
+        }
+    }
+
+    #[test]
+    fn char_width() {
+        assert_eq!(1, width('a'));
+        assert_eq!(1, width('é'));
+        assert_eq!(2, width('中'));
+        assert_eq!(2, width('😀'));
+        assert_eq!(2, width('Ａ'));
+        assert_eq!(1, width('ｱ'));
+        assert_eq!(0, width('\u{301}'));
+        assert_eq!(0, width('\u{7}'));
+    }
+
+    #[test]
+    fn extenders() {
+        assert!(is_extend('\u{301}'));
+        assert!(is_extend('\u{fe0f}'));
+        assert!(is_extend('\u{1f3fd}'));
+        assert!(is_extend('\u{200d}'));
+        assert!(!is_extend('a'));
+    }
+}
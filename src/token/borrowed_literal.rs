@@ -0,0 +1,73 @@
+use std::borrow::Cow;
+
+use super::{number::Number, token_literal::TokenLiteral};
+
+///A [`TokenLiteral`] borrowing from the source it was scanned from
+///
+///Punctuation, numbers and strings without escape sequences point straight into the source, only
+///strings that had to be unescaped own their text.
+///
+///# Examples
+///
+///```
+///# use std::borrow::Cow;
+///# use jtool::token::{borrowed_literal::BorrowedLiteral, token_literal::TokenLiteral};
+///let literal = BorrowedLiteral::String(Cow::Borrowed("Hello, World!"));
+///let owned: TokenLiteral = literal.into();
+///
+///assert_eq!(TokenLiteral::String("Hello, World!".to_string()), owned);
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub enum BorrowedLiteral<'a> {
+    String(Cow<'a, str>),
+    Number(&'a str),
+    Bool(bool),
+    Null,
+}
+
+impl BorrowedLiteral<'_> {
+    ///Whether the literal points into the source rather than owning a copy
+    pub fn is_borrowed(&self) -> bool {
+        !matches!(self, Self::String(Cow::Owned(_)))
+    }
+}
+
+impl From<BorrowedLiteral<'_>> for TokenLiteral {
+    fn from(literal: BorrowedLiteral<'_>) -> Self {
+        match literal {
+            BorrowedLiteral::String(value) => TokenLiteral::String(value.into_owned()),
+            BorrowedLiteral::Number(lexeme) => TokenLiteral::Number(Number::new(lexeme)),
+            BorrowedLiteral::Bool(value) => TokenLiteral::Bool(value),
+            BorrowedLiteral::Null => TokenLiteral::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod borrowed_literal_tests {
+    use std::borrow::Cow;
+
+    use crate::token::{number::Number, token_literal::TokenLiteral};
+
+    use super::BorrowedLiteral;
+
+    #[test]
+    fn borrowed_literals_into_owned() {
+        let string: TokenLiteral = BorrowedLiteral::String(Cow::Borrowed("a")).into();
+        let number: TokenLiteral = BorrowedLiteral::Number("1.10").into();
+        let bool: TokenLiteral = BorrowedLiteral::Bool(true).into();
+        let null: TokenLiteral = BorrowedLiteral::Null.into();
+
+        assert_eq!(TokenLiteral::String("a".to_string()), string);
+        assert_eq!(TokenLiteral::Number(Number::new("1.10")), number);
+        assert_eq!(TokenLiteral::Bool(true), bool);
+        assert_eq!(TokenLiteral::Null, null);
+    }
+
+    #[test]
+    fn is_borrowed() {
+        assert!(BorrowedLiteral::String(Cow::Borrowed("a")).is_borrowed());
+        assert!(BorrowedLiteral::Number("1").is_borrowed());
+        assert!(!BorrowedLiteral::String(Cow::Owned("a".to_string())).is_borrowed());
+    }
+}
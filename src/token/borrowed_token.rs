@@ -0,0 +1,91 @@
+use super::{
+    borrowed_literal::BorrowedLiteral, token::Token, token_position::TokenPosition,
+    token_type::TokenType, trivia::TokenTrivia,
+};
+
+///A [`Token`] borrowing its literal from the source it was scanned from
+///
+///Scanning into borrowed tokens avoids allocating for every token, only strings containing
+///escape sequences need their own copy. Use [`BorrowedToken::into_owned`] to keep a token around
+///for longer than the source.
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::scanner::Scanner;
+///# use jtool::token::borrowed_literal::BorrowedLiteral;
+///let source = String::from(r#"["plain", "esc\"aped"]"#);
+///let tokens = Scanner::new(&source).scan_borrowed().unwrap();
+///
+///assert!(tokens[1].token_literal.is_borrowed());
+///assert!(!tokens[3].token_literal.is_borrowed());
+///```
+#[derive(Debug, PartialEq, Clone)]
+pub struct BorrowedToken<'a> {
+    pub token_type: TokenType,
+    pub token_literal: BorrowedLiteral<'a>,
+    pub token_position: TokenPosition,
+    pub trivia: Option<Box<TokenTrivia<'a>>>,
+}
+
+impl<'a> BorrowedToken<'a> {
+    pub fn new(
+        token_type: TokenType,
+        token_literal: BorrowedLiteral<'a>,
+        token_position: TokenPosition,
+    ) -> Self {
+        Self {
+            token_type,
+            token_literal,
+            token_position,
+            trivia: None,
+        }
+    }
+
+    pub fn into_owned(self) -> Token {
+        Token {
+            trivia: self.trivia.map(|trivia| Box::new(trivia.into_owned())),
+            ..Token::new(
+                self.token_type,
+                self.token_literal.into(),
+                self.token_position,
+            )
+        }
+    }
+}
+
+impl From<BorrowedToken<'_>> for Token {
+    fn from(token: BorrowedToken<'_>) -> Self {
+        token.into_owned()
+    }
+}
+
+#[cfg(test)]
+mod borrowed_token_tests {
+    use std::borrow::Cow;
+
+    use crate::token::{
+        borrowed_literal::BorrowedLiteral, token::Token, token_literal::TokenLiteral,
+        token_position::TokenPosition, token_type::TokenType,
+    };
+
+    use super::BorrowedToken;
+
+    #[test]
+    fn borrowed_token_into_owned() {
+        let borrowed = BorrowedToken::new(
+            TokenType::String,
+            BorrowedLiteral::String(Cow::Borrowed("Hello, World!")),
+            TokenPosition::new(1, 1, 1, 16, 0, 15),
+        );
+
+        assert_eq!(
+            Token::new(
+                TokenType::String,
+                TokenLiteral::String("Hello, World!".to_string()),
+                TokenPosition::new(1, 1, 1, 16, 0, 15),
+            ),
+            borrowed.into_owned()
+        );
+    }
+}
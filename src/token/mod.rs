@@ -1,4 +1,9 @@
+pub mod borrowed_literal;
+pub mod borrowed_token;
+pub mod number;
+#[allow(clippy::module_inception)]
 pub mod token;
 pub mod token_literal;
 pub mod token_position;
 pub mod token_type;
+pub mod trivia;
@@ -0,0 +1,358 @@
+use std::fmt::Display;
+
+///A JSON number kept exactly as it was written
+///
+///Converting a number into `f64` while scanning silently rounds integers above 2^53 and drops
+///trailing zeros e.g. `1.10` becomes `1.1`. `Number` instead stores the original lexeme so a
+///document can be re-emitted unchanged, and offers checked conversions for when a value is
+///needed.
+///
+///# Examples
+///
+///```
+///# use jtool::token::number::Number;
+///let id = Number::new("9007199254740993");
+///
+///assert_eq!(Some(9007199254740993), id.as_i64());
+///assert_eq!("9007199254740993", id.as_str());
+///
+///let price = Number::new("1.10");
+///
+///assert_eq!(None, price.as_i64());
+///assert_eq!("1.10", price.to_decimal().unwrap().to_string());
+///```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Number {
+    lexeme: String,
+}
+
+impl Number {
+    ///Wrap a number lexeme, which is expected to follow the JSON or JSON5 number grammar
+    pub fn new(lexeme: impl Into<String>) -> Self {
+        Self {
+            lexeme: lexeme.into(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.lexeme
+    }
+
+    ///Convert to `i64` if the number is an integer that fits, including forms like `1e3` or `2.0`
+    pub fn as_i64(&self) -> Option<i64> {
+        self.to_decimal()?
+            .to_i128()
+            .and_then(|n| i64::try_from(n).ok())
+    }
+
+    ///Convert to `u64` if the number is a non-negative integer that fits
+    pub fn as_u64(&self) -> Option<u64> {
+        self.to_decimal()?
+            .to_i128()
+            .and_then(|n| u64::try_from(n).ok())
+    }
+
+    ///Convert to the nearest `f64`, failing if the number is out of range
+    ///
+    ///JSON5's `Infinity` and `NaN` convert to their `f64` counterparts.
+    ///
+    ///Note: This is the only lossy conversion, numbers with more precision than `f64` can hold are
+    ///rounded.
+    pub fn as_f64(&self) -> Option<f64> {
+        if self.is_hex() {
+            return self.to_decimal()?.to_string().parse().ok();
+        }
+
+        self.lexeme
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite() || self.is_non_finite())
+    }
+
+    ///Whether this is JSON5's `Infinity` or `NaN`, with or without a sign
+    pub fn is_non_finite(&self) -> bool {
+        matches!(unsigned(&self.lexeme).1, "Infinity" | "NaN")
+    }
+
+    fn is_hex(&self) -> bool {
+        hex_digits(unsigned(&self.lexeme).1).is_some()
+    }
+
+    ///Convert to an arbitrary-precision decimal which keeps every digit of the lexeme
+    ///
+    ///Fails if the lexeme is not a finite number or its exponent does not fit in `i64`, see
+    ///[`Decimal::parse`].
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        Decimal::parse(&self.lexeme)
+    }
+}
+
+///Split the sign off a lexeme, returning whether it was negative
+fn unsigned(lexeme: &str) -> (bool, &str) {
+    match lexeme.as_bytes().first() {
+        Some(b'-') => (true, &lexeme[1..]),
+        Some(b'+') => (false, &lexeme[1..]),
+        _ => (false, lexeme),
+    }
+}
+
+///The digits of an unsigned JSON5 hexadecimal lexeme such as `0x1F`
+fn hex_digits(unsigned: &str) -> Option<&str> {
+    unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.lexeme)
+    }
+}
+
+///An arbitrary-precision decimal number
+///
+///The value is `digits * 10^exponent`, negated when `negative` is set. Trailing zeros are kept so
+///that `1.10` and `1.1` remain distinguishable. The digits are unbounded but the exponent is an
+///`i64`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Decimal {
+    pub negative: bool,
+    pub digits: String,
+    pub exponent: i64,
+}
+
+impl Decimal {
+    ///Parse a number lexeme in the JSON grammar
+    ///
+    ///JSON5's finite forms are accepted too: a leading `+`, a leading or trailing `.` and
+    ///hexadecimal integers.
+    ///
+    ///Returns `None` when the exponent, after shifting it by the number of fraction digits, does
+    ///not fit in `i64` e.g. `1e99999999999999999999`, or when a hexadecimal integer does not fit
+    ///in `u128`.
+    pub fn parse(lexeme: &str) -> Option<Self> {
+        let (negative, unsigned) = unsigned(lexeme);
+
+        if let Some(hex) = hex_digits(unsigned) {
+            if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            return Some(Self {
+                negative,
+                digits: u128::from_str_radix(hex, 16).ok()?.to_string(),
+                exponent: 0,
+            });
+        }
+
+        let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+            Some(index) => (
+                &unsigned[..index],
+                unsigned[index + 1..].parse::<i64>().ok()?,
+            ),
+            None => (unsigned, 0),
+        };
+
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let digits = format!("{}{}", integer, fraction);
+        let digits = match digits.trim_start_matches('0') {
+            "" => "0".to_string(),
+            trimmed => trimmed.to_string(),
+        };
+
+        Some(Self {
+            negative,
+            digits,
+            exponent: exponent.checked_sub(i64::try_from(fraction.len()).ok()?)?,
+        })
+    }
+
+    ///Return the value as an integer, if it is one and fits in `i128`
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.digits.bytes().all(|b| b == b'0') {
+            return Some(0);
+        }
+
+        let mut digits = self.digits.as_str();
+        let mut exponent = self.exponent;
+
+        while exponent < 0 {
+            digits = digits.strip_suffix('0')?;
+            exponent += 1;
+        }
+
+        //`i128::MAX` has 39 digits, checked before the exponent is trusted as a `u32`
+        if exponent.checked_add(i64::try_from(digits.len()).ok()?)? > 39 {
+            return None;
+        }
+
+        let value = digits
+            .parse::<i128>()
+            .ok()?
+            .checked_mul(10_i128.checked_pow(u32::try_from(exponent).ok()?)?)?;
+
+        Some(if self.negative { -value } else { value })
+    }
+}
+
+impl Display for Decimal {
+    ///Format in plain notation, falling back to scientific notation for very large exponents
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+
+        if self.exponent.unsigned_abs() > 32 {
+            return write!(f, "{}e{}", self.digits, self.exponent);
+        }
+
+        if self.exponent >= 0 {
+            return write!(f, "{}{}", self.digits, "0".repeat(self.exponent as usize));
+        }
+
+        let scale = self.exponent.unsigned_abs() as usize;
+
+        if self.digits.len() > scale {
+            let (integer, fraction) = self.digits.split_at(self.digits.len() - scale);
+            write!(f, "{}.{}", integer, fraction)
+        } else {
+            write!(
+                f,
+                "0.{}{}",
+                "0".repeat(scale - self.digits.len()),
+                self.digits
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod number_tests {
+    use super::{Decimal, Number};
+
+    #[test]
+    fn keep_lexeme() {
+        assert_eq!("1.10", Number::new("1.10").as_str());
+        assert_eq!("-2.5E-3", Number::new("-2.5E-3").to_string());
+    }
+
+    #[test]
+    fn convert_to_integers() {
+        assert_eq!(
+            Some(9007199254740993),
+            Number::new("9007199254740993").as_i64()
+        );
+        assert_eq!(Some(i64::MIN), Number::new("-9223372036854775808").as_i64());
+        assert_eq!(None, Number::new("9223372036854775808").as_i64());
+        assert_eq!(Some(u64::MAX), Number::new("18446744073709551615").as_u64());
+        assert_eq!(None, Number::new("-1").as_u64());
+        assert_eq!(Some(0), Number::new("-0").as_u64());
+        assert_eq!(Some(1000), Number::new("1e3").as_i64());
+        assert_eq!(Some(2), Number::new("2.000").as_i64());
+        assert_eq!(Some(12), Number::new("1200e-2").as_i64());
+        assert_eq!(None, Number::new("1.5").as_i64());
+        assert_eq!(None, Number::new("1e400").as_i64());
+        assert_eq!(Some(0), Number::new("0.00").as_i64());
+        assert_eq!(Some(0), Number::new("-0.000").as_i64());
+        assert_eq!(Some(0), Number::new("0e3000000000").as_i64());
+        assert_eq!(Some(0), Number::new("0e999999999999999").as_i64());
+        assert_eq!(None, Number::new("1e999999999999999").as_i64());
+        assert_eq!(None, Number::new("1e-999999999999999").as_i64());
+        assert_eq!(
+            Some(i128::MAX),
+            Number::new(i128::MAX.to_string())
+                .to_decimal()
+                .unwrap()
+                .to_i128()
+        );
+    }
+
+    #[test]
+    fn convert_to_float() {
+        assert_eq!(Some(-2.5e-3), Number::new("-2.5E-3").as_f64());
+        assert_eq!(Some(1.1), Number::new("1.10").as_f64());
+        assert_eq!(None, Number::new("1e400").as_f64());
+    }
+
+    #[test]
+    fn convert_json5_numbers() {
+        assert_eq!(Some(31), Number::new("0x1F").as_i64());
+        assert_eq!(Some(-255), Number::new("-0XFF").as_i64());
+        assert_eq!(Some(255.0), Number::new("+0xff").as_f64());
+        assert_eq!(Some(5), Number::new("+5").as_u64());
+        assert_eq!(Some(0.5), Number::new(".5").as_f64());
+        assert_eq!(Some(5), Number::new("5.").as_i64());
+        assert_eq!("0.5", Number::new(".5").to_decimal().unwrap().to_string());
+        assert_eq!(None, Number::new(".").to_decimal());
+        assert_eq!(None, Number::new("0x").to_decimal());
+        assert_eq!(None, Number::new("0xg").to_decimal());
+
+        assert_eq!(Some(f64::INFINITY), Number::new("Infinity").as_f64());
+        assert_eq!(Some(f64::NEG_INFINITY), Number::new("-Infinity").as_f64());
+        assert!(Number::new("+NaN").as_f64().unwrap().is_nan());
+        assert!(Number::new("-Infinity").is_non_finite());
+        assert!(!Number::new("1e400").is_non_finite());
+        assert_eq!(None, Number::new("NaN").to_decimal());
+        assert_eq!(None, Number::new("Infinity").as_i64());
+    }
+
+    #[test]
+    fn convert_to_decimal() {
+        assert_eq!(
+            Some(Decimal {
+                negative: true,
+                digits: "110".to_string(),
+                exponent: -2,
+            }),
+            Number::new("-1.10").to_decimal()
+        );
+
+        assert_eq!(
+            "-1.10",
+            Number::new("-1.10").to_decimal().unwrap().to_string()
+        );
+        assert_eq!(
+            "0.0025",
+            Number::new("2.5e-3").to_decimal().unwrap().to_string()
+        );
+        assert_eq!(
+            "1200",
+            Number::new("12E2").to_decimal().unwrap().to_string()
+        );
+        assert_eq!(
+            "0.00",
+            Number::new("0.00").to_decimal().unwrap().to_string()
+        );
+        assert_eq!(
+            "123456789012345678901234567890.5",
+            Number::new("123456789012345678901234567890.5")
+                .to_decimal()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "1e400",
+            Number::new("1e400").to_decimal().unwrap().to_string()
+        );
+        assert_eq!(
+            "1e-9223372036854775808",
+            Number::new("1e-9223372036854775808")
+                .to_decimal()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(None, Number::new("1e99999999999999999999").to_decimal());
+        assert_eq!(None, Number::new("1.5e-9223372036854775808").to_decimal());
+        assert_eq!(None, Number::new("abc").to_decimal());
+    }
+}
@@ -1,4 +1,7 @@
-use super::{token_literal::TokenLiteral, token_position::TokenPosition, token_type::TokenType};
+use super::{
+    token_literal::TokenLiteral, token_position::TokenPosition, token_type::TokenType,
+    trivia::TokenTrivia,
+};
 
 ///Encapsulate scanned words
 ///
@@ -8,23 +11,30 @@ use super::{token_literal::TokenLiteral, token_position::TokenPosition, token_ty
 ///# Examples
 ///
 ///```
+///# use jtool::token::{
+///#     token::Token, token_literal::TokenLiteral, token_position::TokenPosition,
+///#     token_type::TokenType,
+///# };
 ///let t1 = Token::new(
 ///    TokenType::String,
 ///    TokenLiteral::String("Hello, World!".to_string()),
-///    TokenPosition::new(1, 1, 15),
+///    TokenPosition::new(1, 1, 1, 15, 0, 14),
 ///);
 ///
 ///let t2 = Token::new(
-///    TokenType::String,
+///    TokenType::Null,
 ///    TokenLiteral::Null,
-///    TokenPosition::new(1, 1, 4),
+///    TokenPosition::new(1, 1, 1, 4, 0, 3),
 ///);
 ///```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub token_literal: TokenLiteral,
     pub token_position: TokenPosition,
+    ///The token's source text and surrounding whitespace and comments, only kept when
+    ///[`ScannerConfig::trivia`](crate::scanner::scanner_config::ScannerConfig::trivia) is set
+    pub trivia: Option<Box<TokenTrivia<'static>>>,
 }
 
 impl Token {
@@ -37,6 +47,7 @@ impl Token {
             token_type,
             token_literal,
             token_position,
+            trivia: None,
         }
     }
 }
@@ -54,7 +65,7 @@ mod token_tests {
         let t1 = Token::new(
             TokenType::String,
             TokenLiteral::String("Hello, World!".to_string()),
-            TokenPosition::new(1, 1, 15),
+            TokenPosition::new(1, 1, 1, 15, 0, 14),
         );
 
         assert_eq!(TokenType::String, t1.token_type);
@@ -62,6 +73,6 @@ mod token_tests {
             TokenLiteral::String("Hello, World!".to_string()),
             t1.token_literal
         );
-        assert_eq!(TokenPosition::new(1, 1, 15), t1.token_position)
+        assert_eq!(TokenPosition::new(1, 1, 1, 15, 0, 14), t1.token_position)
     }
 }
@@ -1,3 +1,5 @@
+use super::number::Number;
+
 /// Represent JSON literal values
 ///
 ///JSON literal values e.g. (true, false, null, STRING, NUMBER) need representation in rust as
@@ -5,20 +7,21 @@
 ///two allowing these json values to "exist" in rust. All `TokenLiteral` types can be converted
 ///into strings for convenience.
 ///
-///Note: All numbers are of type `f64`, which still allows non-decimal numbers to be displayed
-///correctly when converted to a string.
+///Note: Numbers are kept as a [`Number`], which stores the exact lexeme from the source so
+///converting a literal back into a string never changes a number.
 ///
 ///# Examples
 ///
 ///```
-///let null: TokenType = TokenType::Null
-///let null: String = TokenType::Null.into();
+///# use jtool::token::token_literal::TokenLiteral;
+///let null: TokenLiteral = TokenLiteral::Null;
+///let null: String = null.into();
 ///```
 ///
 #[derive(Debug, PartialEq, Clone)]
 pub enum TokenLiteral {
     String(String),
-    Number(f64),
+    Number(Number),
     Bool(bool),
     Null,
 }
@@ -36,13 +39,16 @@ impl From<TokenLiteral> for String {
 
 #[cfg(test)]
 mod token_literal_tests {
+    use crate::token::number::Number;
+
     use super::TokenLiteral;
 
     #[test]
     fn token_literals_into_string() {
         let string_literal: String = TokenLiteral::String("Hello, World!".to_string()).into();
-        let number_literal: String = TokenLiteral::Number(100.0).into();
-        let float_literal: String = TokenLiteral::Number(123.456).into();
+        let number_literal: String = TokenLiteral::Number(Number::new("100")).into();
+        let float_literal: String = TokenLiteral::Number(Number::new("123.456")).into();
+        let exact_literal: String = TokenLiteral::Number(Number::new("1.10")).into();
         let false_literal: String = TokenLiteral::Bool(false).into();
         let true_literal: String = TokenLiteral::Bool(true).into();
         let null_literal: String = TokenLiteral::Null.into();
@@ -50,6 +56,7 @@ mod token_literal_tests {
         assert_eq!(String::from("Hello, World!"), string_literal);
         assert_eq!(String::from("100"), number_literal);
         assert_eq!(String::from("123.456"), float_literal);
+        assert_eq!(String::from("1.10"), exact_literal);
         assert_eq!(String::from("null"), null_literal);
         assert_eq!(String::from("false"), false_literal);
         assert_eq!(String::from("true"), true_literal);
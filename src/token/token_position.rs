@@ -1,21 +1,71 @@
 ///Track a tokens position within its environment
-#[derive(Debug, PartialEq)]
+///
+///Lines and columns are 1-based, with columns counted in the scanner's
+///[`ColumnUnit`](crate::source_map::column_unit::ColumnUnit), Unicode scalar values by default.
+///Offsets are 0-based byte offsets into the source. The end of a position is exclusive, so `column_end` and `offset_end`
+///point just past the last character of the token. A token can span several lines, in which case
+///`column_end` is a column on `line_end`.
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct TokenPosition {
-    pub line: i32,
-    pub column_start: i32,
-    pub column_end: i32,
-    pub span: i32,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub offset_start: usize,
+    pub offset_end: usize,
 }
 
 impl TokenPosition {
-    pub fn new(line: i32, column_start: i32, column_end: i32) -> Self {
+    pub fn new(
+        line_start: usize,
+        line_end: usize,
+        column_start: usize,
+        column_end: usize,
+        offset_start: usize,
+        offset_end: usize,
+    ) -> Self {
         Self {
-            line,
+            line_start,
+            line_end,
             column_start,
             column_end,
-            span: column_end - column_start,
+            offset_start,
+            offset_end,
         }
     }
+
+    ///Length of the token in bytes
+    pub fn span(&self) -> usize {
+        self.offset_end - self.offset_start
+    }
+
+    pub fn is_multi_line(&self) -> bool {
+        self.line_start != self.line_end
+    }
+
+    ///Smallest position covering both `self` and `other`
+    pub fn to(&self, other: &TokenPosition) -> TokenPosition {
+        let (start, end) = if self.offset_start <= other.offset_start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let end = if end.offset_end >= start.offset_end {
+            end
+        } else {
+            start
+        };
+
+        TokenPosition::new(
+            start.line_start,
+            end.line_end,
+            start.column_start,
+            end.column_end,
+            start.offset_start,
+            end.offset_end,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -24,11 +74,25 @@ mod token_position_tests {
 
     #[test]
     fn create_new_token_position() {
-        let token_position = TokenPosition::new(1, 1, 4);
+        let token_position = TokenPosition::new(1, 1, 1, 4, 0, 3);
 
-        assert_eq!(1, token_position.line);
+        assert_eq!(1, token_position.line_start);
+        assert_eq!(1, token_position.line_end);
         assert_eq!(1, token_position.column_start);
         assert_eq!(4, token_position.column_end);
-        assert_eq!(3, token_position.span);
+        assert_eq!(0, token_position.offset_start);
+        assert_eq!(3, token_position.offset_end);
+        assert_eq!(3, token_position.span());
+        assert!(!token_position.is_multi_line());
+    }
+
+    #[test]
+    fn join_token_positions() {
+        let key = TokenPosition::new(1, 1, 3, 8, 2, 7);
+        let value = TokenPosition::new(2, 3, 5, 2, 14, 30);
+
+        assert_eq!(TokenPosition::new(1, 3, 3, 2, 2, 30), key.to(&value));
+        assert_eq!(TokenPosition::new(1, 3, 3, 2, 2, 30), value.to(&key));
+        assert!(key.to(&value).is_multi_line());
     }
 }
@@ -1,4 +1,6 @@
-#[derive(Debug, PartialEq)]
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum TokenType {
     LeftBrace,
     RightBrace,
@@ -12,9 +14,33 @@ pub enum TokenType {
     String,
     Number,
     Identifier,
+    Comment,
     Eof,
 }
 
+impl Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::LeftBrace => "`{`",
+            Self::RightBrace => "`}`",
+            Self::LeftBracket => "`[`",
+            Self::RightBracket => "`]`",
+            Self::Colon => "`:`",
+            Self::Comma => "`,`",
+            Self::True => "`true`",
+            Self::False => "`false`",
+            Self::Null => "`null`",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Identifier => "identifier",
+            Self::Comment => "comment",
+            Self::Eof => "end of input",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
 #[cfg(test)]
 mod token_type_tests {
     use crate::token::token_type::TokenType;
@@ -24,4 +50,10 @@ mod token_type_tests {
         assert_eq!(TokenType::LeftBrace, TokenType::LeftBrace);
         assert_ne!(TokenType::Identifier, TokenType::String);
     }
+
+    #[test]
+    fn token_type_display() {
+        assert_eq!("`{`", TokenType::LeftBrace.to_string());
+        assert_eq!("end of input", TokenType::Eof.to_string());
+    }
 }
@@ -0,0 +1,155 @@
+use std::{borrow::Cow, fmt::Display};
+
+use super::token_position::TokenPosition;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TriviaKind {
+    ///A run of spaces and tabs, or any other whitespace the dialect allows
+    Whitespace,
+    ///A single line ending, `\n`, `\r\n` or `\r`
+    Newline,
+    ///A `//` or `/* */` comment including its delimiters
+    Comment,
+}
+
+///Source text between tokens that carries no meaning but is needed to reproduce the input
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub text: Cow<'a, str>,
+    pub position: TokenPosition,
+}
+
+impl<'a> Trivia<'a> {
+    pub fn new(kind: TriviaKind, text: impl Into<Cow<'a, str>>, position: TokenPosition) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            position,
+        }
+    }
+
+    pub fn into_owned(self) -> Trivia<'static> {
+        Trivia {
+            kind: self.kind,
+            text: Cow::Owned(self.text.into_owned()),
+            position: self.position,
+        }
+    }
+}
+
+///Everything a token covers in the source when trivia is kept
+///
+///Trivia after a token up to and including the end of its line is trailing, everything else
+///before the next token is leading, so a comment at the end of a line stays with the value it
+///describes. Writing out the leading trivia, the lexeme and the trailing trivia of every token in
+///order reproduces the source byte for byte.
+///
+///# Examples
+///
+///```
+///# use jtool::scanner::{dialect::Dialect, scanner::Scanner, scanner_config::ScannerConfig};
+///let source = "[1, // one\n 2]\n";
+///let config = ScannerConfig {
+///    dialect: Dialect::Jsonc,
+///    trivia: true,
+///    ..Default::default()
+///};
+///
+///let tokens = Scanner::with_config(source, config).scan().unwrap();
+///let comma = tokens[2].trivia.as_ref().unwrap();
+///
+///assert_eq!(",", comma.lexeme);
+///assert_eq!(" // one\n", comma.to_string().trim_start_matches(','));
+///
+///let round_trip: String = tokens
+///    .iter()
+///    .map(|token| token.trivia.as_ref().unwrap().to_string())
+///    .collect();
+///
+///assert_eq!(source, round_trip);
+///```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TokenTrivia<'a> {
+    pub leading: Vec<Trivia<'a>>,
+    ///The token exactly as written, e.g. a string with its quotes and escape sequences
+    pub lexeme: Cow<'a, str>,
+    pub trailing: Vec<Trivia<'a>>,
+}
+
+impl TokenTrivia<'_> {
+    pub fn into_owned(self) -> TokenTrivia<'static> {
+        TokenTrivia {
+            leading: self.leading.into_iter().map(Trivia::into_owned).collect(),
+            lexeme: Cow::Owned(self.lexeme.into_owned()),
+            trailing: self.trailing.into_iter().map(Trivia::into_owned).collect(),
+        }
+    }
+}
+
+impl Display for TokenTrivia<'_> {
+    ///Write the source text covered by the token and its trivia
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for trivia in &self.leading {
+            f.write_str(&trivia.text)?;
+        }
+
+        f.write_str(&self.lexeme)?;
+
+        for trivia in &self.trailing {
+            f.write_str(&trivia.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod trivia_tests {
+    use std::borrow::Cow;
+
+    use crate::token::token_position::TokenPosition;
+
+    use super::{TokenTrivia, Trivia, TriviaKind};
+
+    #[test]
+    fn write_token_trivia() {
+        let trivia = TokenTrivia {
+            leading: vec![
+                Trivia::new(
+                    TriviaKind::Newline,
+                    "\r\n",
+                    TokenPosition::new(1, 2, 1, 1, 0, 2),
+                ),
+                Trivia::new(
+                    TriviaKind::Whitespace,
+                    "  ",
+                    TokenPosition::new(2, 2, 1, 3, 2, 4),
+                ),
+            ],
+            lexeme: Cow::Borrowed("\"a\\n\""),
+            trailing: vec![Trivia::new(
+                TriviaKind::Comment,
+                "/**/",
+                TokenPosition::new(2, 2, 8, 12, 9, 13),
+            )],
+        };
+
+        assert_eq!("\r\n  \"a\\n\"/**/", trivia.to_string());
+    }
+
+    #[test]
+    fn trivia_into_owned() {
+        let text = String::from("// note");
+        let trivia = Trivia::new(
+            TriviaKind::Comment,
+            text.as_str(),
+            TokenPosition::new(1, 1, 1, 8, 0, 7),
+        );
+
+        let owned: Trivia<'static> = trivia.clone().into_owned();
+
+        assert_eq!(trivia, owned);
+        assert!(matches!(owned.text, Cow::Owned(_)));
+    }
+}